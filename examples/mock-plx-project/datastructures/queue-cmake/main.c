@@ -0,0 +1,6 @@
+
+#include "queue.h"
+int main(void)
+{
+	queue_push(10);
+}
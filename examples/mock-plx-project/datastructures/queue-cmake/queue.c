@@ -0,0 +1,3 @@
+void queue_push(int value)
+{
+}
@@ -0,0 +1,6 @@
+#ifndef QUEUE_H
+
+#define QUEUE_H
+
+void queue_push(int);
+#endif
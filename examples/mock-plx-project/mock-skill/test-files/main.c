@@ -0,0 +1,8 @@
+#include <stdio.h>
+
+int main(void) {
+    int number = 0;
+    scanf("%d", &number);
+    printf("%d\n", number * 2);
+    return 0;
+}
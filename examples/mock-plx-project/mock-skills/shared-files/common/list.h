@@ -0,0 +1 @@
+int list_len(void);
@@ -0,0 +1 @@
+int common_value(void) { return 1; }
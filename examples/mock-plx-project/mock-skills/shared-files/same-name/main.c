@@ -0,0 +1,4 @@
+int common_value(void);
+int exo_value(void);
+
+int main(void) { return common_value() + exo_value() - 3; }
@@ -0,0 +1 @@
+int exo_value(void) { return 2; }
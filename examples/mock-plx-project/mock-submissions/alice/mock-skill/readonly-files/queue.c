@@ -0,0 +1 @@
+void push(int value) {}
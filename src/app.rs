@@ -1,12 +1,18 @@
 pub mod app;
+mod bless;
 mod check;
 mod compilation;
 mod errors;
 mod exo_check_result;
 mod exo_status_report;
 mod file_watcher;
+mod grade;
+pub mod gradebook;
+pub mod headless;
 mod key_press;
 mod navigation;
+pub mod report;
 mod run;
 mod transitions;
 mod utils;
+mod verify_solutions;
@@ -1,7 +1,7 @@
 use crate::{
     core::{
         check::checker::Checker,
-        compiler::compile_runner::CompileRunner,
+        compiler::{compile_runner::CompileRunner, diagnostic::Diagnostic, toolchain::Toolchain},
         core_error::CoreInitError,
         editor::opener::EditorOpener,
         file_utils::{build_folder::generate_build_folder, file_utils::current_folder},
@@ -12,11 +12,11 @@ use crate::{
     },
     models::{
         check_state::CheckStatus, constants::TARGET_FILE_BASE_NAME, event::Event, exo::Exo,
-        project::Project, ui_state::UiState,
+        project::Project, ui_state::UiState, user_config::UserConfig,
     },
     ui::ui::Ui,
 };
-use log::{error, info};
+use log::{error, info, warn};
 use std::{
     path::PathBuf,
     sync::{
@@ -40,44 +40,64 @@ pub struct App {
     pub(super) ui_state_tx: Sender<UiState>,
     pub(super) run: bool,
     pub(super) current_run: Option<ExoStatusReport>,
+    pub(super) toolchain: Toolchain,
+    pub(super) startup_errors: Vec<String>,
 }
 
 impl App {
     ///  Create a new App instance
     ///
     /// This function will create a new App instance and initialize the project
-    /// It will succeed if the project is found in the current folder
+    /// It will succeed if the project is found in `course_folder`
+    /// The current folder is changed to `course_folder`, so build folders are generated there
     ///
     /// # Returns
     /// A Result containing the App instance if the project is found or an   
     /// error if the project is not found
     ///
-    pub fn new() -> Result<Self, CoreInitError> {
-        let current_folder = match current_folder() {
-            Ok(folder) => folder,
-            Err(_err) => return Err(CoreInitError::PlxProjNotFound), // TODO maybe be more specific
-                                                                     // here by adding the error detail
-        };
-        // TODO these warnings should be accessible to the user
-        let (project, _warnings) = match Project::from_dir(&current_folder) {
+    pub fn new(course_folder: &std::path::Path) -> Result<Self, CoreInitError> {
+        // TODO maybe be more specific here by adding the error detail
+        std::env::set_current_dir(course_folder).map_err(|_| CoreInitError::PlxProjNotFound)?;
+        let current_folder = current_folder().map_err(|_| CoreInitError::PlxProjNotFound)?;
+        let (project, warnings) = match Project::from_dir(&current_folder) {
             Ok((project, warnings)) => (project, warnings),
-            Err((err, _warnings)) => {
-                // TODO handle these warnings even in case of failure
-                return Err(CoreInitError::ProjFilesParsingError(format!("{:?}", err)));
+            Err((err, warnings)) => {
+                warnings.iter().for_each(|warning| warn!("{}", warning));
+                return Err(CoreInitError::ProjFilesParsingError(format!(
+                    "{}, run `plx validate` for more details",
+                    err
+                )));
             }
         };
+        warnings.iter().for_each(|warning| warn!("{}", warning));
+        // Skills and exos that couldn't be parsed are missing from the lists, tell the user why
+        let mut startup_errors: Vec<String> = warnings
+            .iter()
+            .filter(|warning| warning.is_error())
+            .map(|warning| warning.to_string())
+            .collect();
+        let config = UserConfig::load().unwrap_or_else(|err| {
+            startup_errors.push(format!("Couldn't load the PLX config file: {}", err));
+            UserConfig::default()
+        });
+        // Report missing compilers now instead of failing silently when compiling
+        startup_errors.append(&mut config.toolchain.find_missing(&project.compilers()));
+        startup_errors.iter().for_each(|err| error!("{}", err));
+
         let (event_tx, event_rx) = mpsc::channel();
         let (ui_state_tx, ui_state_rx) = mpsc::channel();
         let work_handler = WorkHandler::new(event_tx.clone());
 
         let mut app = App {
-            ui_state: UiState::Home,
+            ui_state: UiState::Home { errors: vec![] },
             project,
             work_handler,
             event_rx,
             ui_state_tx,
             run: true,
             current_run: None,
+            toolchain: config.toolchain,
+            startup_errors,
         };
         app.start_ui(ui_state_rx);
         Ok(app)
@@ -97,9 +117,10 @@ impl App {
     ///
     ///
     pub(super) fn resume_last_exo(&mut self) {
-        if let Some(exo) = &self.project.resume() {
+        // A locked exo can't be resumed, e.g. after a requirement was added to the course
+        if let Some(exo) = &self.project.resume().filter(|exo| exo.locked.is_none()) {
             //TODO refactor this code (duplicate)
-            match App::start_exo(&self.work_handler, exo) {
+            match App::start_exo(&self.work_handler, &self.toolchain, exo) {
                 Ok(cr) => {
                     self.current_run = Some(cr);
                     self.go_to_compiling();
@@ -137,6 +158,7 @@ impl App {
                     Event::RunEnd(id) => self.on_run_end(id),
                     Event::RunOutputLine(id, line) => self.on_run_output(id, line),
                     Event::RunFail(run_id, err) => self.on_run_fail(run_id, err),
+                    Event::SubmissionGraded(..) => {} // Only sent by `plx grade`
                 }
             }
         }
@@ -186,6 +208,21 @@ impl App {
         }
         None
     }
+    /// Opens the default editor at the location of a compiler diagnostic using a worker
+    ///
+    /// Returns the id of the worker if it was successfully started
+    /// else it returns None
+    pub(super) fn open_editor_at(
+        wh: &Arc<Mutex<WorkHandler>>,
+        diagnostic: &Diagnostic,
+    ) -> Option<usize> {
+        let opener = EditorOpener::new_default_editor_at(
+            diagnostic.file.clone(),
+            diagnostic.line,
+            diagnostic.column,
+        )?;
+        App::start_work(wh, Box::new(opener))
+    }
     /// Compiles the exo using a worker
     ///
     /// This function doesn't block, the compilation will be done using a new worker
@@ -194,29 +231,49 @@ impl App {
     /// else it returns an error describing why it failed
     pub(super) fn compile(
         wh: &Arc<Mutex<WorkHandler>>,
+        toolchain: &Toolchain,
         exo: &Exo,
     ) -> Result<PathBuf, CompilationStartError> {
-        let compiler = exo
-            .compiler()
-            .ok_or(CompilationStartError::CompilerNotSupported)?;
-        info!("Compiler: {:#?}", compiler);
+        let (runner, output_path) = App::prepare_compilation(toolchain, exo)?;
+        App::start_work(wh, Box::new(runner))
+            .ok_or(CompilationStartError::ErrorStartingCompileProcess)?;
+        return Ok(output_path);
+    }
 
+    /// Creates the build folder of the exo and the compile runner able to build it
+    ///
+    /// Returns the runner and the path of the target binary it will produce
+    pub(super) fn prepare_compilation(
+        toolchain: &Toolchain,
+        exo: &Exo,
+    ) -> Result<(CompileRunner, PathBuf), CompilationStartError> {
         let folder = generate_build_folder(exo).map_err(|err| {
             error!("Error generation build folder ({})", err);
             CompilationStartError::BuildFolderGenerationFailed
         })?;
+        App::prepare_compilation_in(toolchain, exo, &folder)
+    }
+
+    /// Same as `prepare_compilation` but the artifacts are generated in the given `folder`
+    pub(super) fn prepare_compilation_in(
+        toolchain: &Toolchain,
+        exo: &Exo,
+        folder: &std::path::Path,
+    ) -> Result<(CompileRunner, PathBuf), CompilationStartError> {
+        let compiler = exo
+            .compiler()
+            .ok_or(CompilationStartError::CompilerNotSupported)?;
+        info!("Compiler: {:#?}", compiler);
         info!("Folder: {:#?}", folder);
         let output_path = if cfg!(windows) {
             folder.join(format!("{}.exe", TARGET_FILE_BASE_NAME))
         } else {
             folder.join(TARGET_FILE_BASE_NAME)
         };
-        let runner = CompileRunner::new(&compiler, exo, &output_path)
+        let runner = CompileRunner::new(&compiler, toolchain, exo, &output_path)
             .ok_or(CompilationStartError::ErrorStartingCompileProcess)?;
         info!("Command: {:#?}", runner.get_full_command());
-        App::start_work(wh, Box::new(runner))
-            .ok_or(CompilationStartError::ErrorStartingCompileProcess)?;
-        return Ok(output_path);
+        Ok((runner, output_path))
     }
 
     /// Cleans the previous run by stopping and waiting for every non UI worker to finish
@@ -243,19 +300,37 @@ impl App {
     /// else it returns an error
     pub(super) fn start_exo(
         wh: &Arc<Mutex<WorkHandler>>,
+        toolchain: &Toolchain,
         exo: &Exo,
     ) -> Result<ExoStatusReport, StartExoFail> {
         App::cleanup_previous_run(wh);
+        // Keep the starting state of the exo so it can be reset, see `App::reset_exo`
+        if let Err(err) = exo.save_original_files() {
+            warn!(
+                "Couldn't save the original files of {:?}: {}",
+                exo.folder, err
+            );
+        }
         // Open editor, Compile exo and start watchers
         // TODO warn user if we couldn't open editor but ignore error for now so it doesn't stop us
         // from launching
         let _ = App::open_editor(wh, exo); // Ignore Error while opening editor for now
-        let output_path =
-            App::compile(wh, exo).map_err(|err| StartExoFail::CouldNotStartCompilation(err))?;
+        let output_path = App::compile(wh, toolchain, exo)
+            .map_err(|err| StartExoFail::CouldNotStartCompilation(err))?;
         App::start_watcher(wh, exo);
 
         Ok(ExoStatusReport::new(exo, output_path))
     }
+    /// Restores the original files of the exo and resets its progress
+    /// Returns false if the original files were never saved, nothing is reset then
+    /// The progress is only reset once the files are restored, so they always match
+    pub(crate) fn reset_exo(exo: &Exo) -> std::io::Result<bool> {
+        let restored = exo.restore_original_files()?;
+        if restored {
+            Project::reset_exo_state(exo);
+        }
+        Ok(restored)
+    }
     /// Runs the target file generated at the compilation step
     /// Here we launch multiple instances of the target file, one for each exo check
     ///
@@ -267,7 +342,13 @@ impl App {
                 .enumerate()
                 .for_each(|(id, result)| {
                     if let Some(worker) =
-                        Launcher::new(id, cr.elf_path.clone(), result.state.check.args.clone())
+                        Launcher::new(id, cr.elf_path.clone(), result.state.check.args.clone()).map(
+                            |launcher| {
+                                launcher
+                                    .with_timeout(cr.exo.timeout)
+                                    .with_stdin(result.state.check.stdin.clone())
+                            },
+                        )
                     {
                         if App::start_work(&self.work_handler, Box::new(worker)).is_some() {
                             result.state.status = CheckStatus::Running;
@@ -289,7 +370,8 @@ impl App {
                     id,
                     Arc::clone(&cr.check_results[id].state.check),
                     cr.check_results[id].output.join("\n"),
-                );
+                )
+                .with_comparison(cr.exo.comparison);
                 return Some(App::start_work(&self.work_handler, Box::new(checker))?);
             }
         }
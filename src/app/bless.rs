@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use console::style;
+
+use crate::{
+    core::{
+        diff::diff::Diff,
+        file_utils::file_utils::{read_file, write_file},
+        parser::toml_editor::{set_cases_expected, set_checks_expected},
+    },
+    models::{check::ExpectedSource, check_state::CheckStatus, constants::EXO_INFO_FILE, exo::Exo},
+};
+
+use super::headless::Headless;
+
+/// Functions related to generating the expected outputs of the checks from the solutions
+impl Headless {
+    /// Runs the solution of every exo inside the target folder and writes its output as the
+    /// expected output of the checks that don't match it
+    /// With `dry_run`, the changes are only printed and exo.toml files are not modified
+    /// Returns false if the solution of an exo couldn't be run
+    pub fn bless(&self, dry_run: bool) -> bool {
+        let exos = self.target_exos();
+        if exos.is_empty() {
+            eprintln!(
+                "{} no exo found in {:?}",
+                style("error:").red(),
+                self.target
+            );
+            return false;
+        }
+        // Bless every exo even if one of them fails
+        let failed = exos
+            .iter()
+            .filter(|exo| !self.bless_exo(exo, dry_run))
+            .count();
+        failed == 0
+    }
+
+    fn bless_exo(&self, exo: &Exo, dry_run: bool) -> bool {
+        println!("{}", style(&exo.name).bold());
+        if exo.solutions.is_empty() {
+            println!("  {} no solution found", style("✗").red());
+            return false;
+        }
+        let run = match self.run_solution(exo) {
+            Ok(run) => run,
+            Err(err) => {
+                println!(
+                    "  {} couldn't copy the solution ({})",
+                    style("✗").red(),
+                    err
+                );
+                return false;
+            }
+        };
+        if let Some(err) = &run.compilation_error {
+            println!("  {} {}", style("✗").red(), err);
+            run.report
+                .compilation_output
+                .iter()
+                .for_each(|line| println!("    {}", line));
+            return false;
+        }
+
+        let mut success = true;
+        let mut changes = Vec::new();
+        let mut case_changes = Vec::new();
+        for result in run.report.check_results.iter() {
+            let name = &result.state.check.name;
+            match &result.state.status {
+                CheckStatus::Passed => println!("  {} {}", style("=").dim(), name),
+                CheckStatus::Failed(expected, output, _) => {
+                    println!("  {} {}", style("~").yellow(), name);
+                    if dry_run {
+                        Diff::calculate_difference(expected, output, None)
+                            .to_ansi_colors()
+                            .lines()
+                            .for_each(|line| println!("      {}", line));
+                    }
+                    match &result.state.check.source {
+                        ExpectedSource::Check(check_idx) => {
+                            changes.push((*check_idx, output.clone()))
+                        }
+                        ExpectedSource::Case(check_idx, case_idx) => {
+                            case_changes.push((*check_idx, *case_idx, output.clone()))
+                        }
+                        ExpectedSource::File(file) if !dry_run => {
+                            success &= Headless::bless_file(file, output)
+                        }
+                        ExpectedSource::File(_) => {}
+                        ExpectedSource::Inherited => println!(
+                            "    {}",
+                            style("inherited check, update it in skill.toml or course.toml").dim()
+                        ),
+                    }
+                }
+                CheckStatus::RunFail(err) => {
+                    println!("  {} {} (couldn't run: {})", style("✗").red(), name, err);
+                    success = false;
+                }
+                _ => {
+                    println!("  {} {} (not run)", style("✗").red(), name);
+                    success = false;
+                }
+            }
+        }
+        if (changes.is_empty() && case_changes.is_empty()) || dry_run {
+            return success;
+        }
+
+        let exo_info_file = exo.folder.join(EXO_INFO_FILE);
+        let written = read_file(&exo_info_file)
+            .map_err(|err| err.to_string())
+            .and_then(|content| {
+                set_checks_expected(&content, &changes)
+                    .and_then(|content| set_cases_expected(&content, &case_changes))
+                    .map_err(|err| err.to_string())
+            })
+            .and_then(|content| {
+                write_file(&exo_info_file, &content).map_err(|err| err.to_string())
+            });
+        match written {
+            Ok(()) => {
+                println!(
+                    "  {} checks updated in {:?}",
+                    changes.len() + case_changes.len(),
+                    exo_info_file
+                );
+                success
+            }
+            Err(err) => {
+                println!(
+                    "  {} couldn't update {:?} ({})",
+                    style("✗").red(),
+                    exo_info_file,
+                    err
+                );
+                false
+            }
+        }
+    }
+
+    /// Writes `output` as the expected output in `file`
+    fn bless_file(file: &PathBuf, output: &str) -> bool {
+        match write_file(file, &format!("{}\n", output)) {
+            Ok(()) => {
+                println!("    updated {:?}", file);
+                true
+            }
+            Err(err) => {
+                println!(
+                    "    {} couldn't update {:?} ({})",
+                    style("✗").red(),
+                    file,
+                    err
+                );
+                false
+            }
+        }
+    }
+}
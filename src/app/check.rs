@@ -56,7 +56,7 @@ impl App {
     /// It is used to update the ui state and send it to the frontend
     /// See `on_check_passed` and `on_check_failed` functions
     /// This function updates the UI state
-    fn on_new_check_update(&mut self) {
+    pub(super) fn on_new_check_update(&mut self) {
         if let Some(ref cr) = self.current_run {
             // Keep the same scroll offset if we're already checking the results
             let scroll_offset = match self.ui_state {
@@ -1,3 +1,5 @@
+use crate::core::compiler::diagnostic::Diagnostic;
+
 use super::app::App;
 
 /// Functions related to handling compilation events
@@ -31,12 +33,15 @@ impl App {
                 );
             }
         } else {
-            let output = if let Some(ref cr) = self.current_run {
-                cr.compilation_output.join("\n")
+            let (output, diagnostics) = if let Some(ref cr) = self.current_run {
+                (
+                    cr.compilation_output.join("\n"),
+                    Diagnostic::parse_output(&cr.compilation_output, &cr.exo.folder),
+                )
             } else {
-                String::from("")
+                (String::from(""), vec![])
             };
-            self.go_to_compilation_error(0, output)
+            self.go_to_compilation_error(0, output, diagnostics, 0)
         }
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::models::{check::Check, check_state::CheckState};
 
 /// ExoCheckResult
@@ -8,6 +10,8 @@ use crate::models::{check::Check, check_state::CheckState};
 pub(super) struct ExoCheckResult {
     pub(super) state: CheckState,
     pub(super) output: Vec<String>,
+    /// Time taken to run and check the output, None if the check wasn't run
+    pub(super) duration: Option<Duration>,
 }
 
 impl ExoCheckResult {
@@ -16,6 +20,7 @@ impl ExoCheckResult {
         Self {
             state: CheckState::new(check),
             output: Vec::new(),
+            duration: None,
         }
     }
 }
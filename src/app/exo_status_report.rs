@@ -1,6 +1,9 @@
 use std::{path::PathBuf, sync::Arc};
 
-use crate::models::{check_state::CheckState, exo::Exo};
+use crate::{
+    core::compiler::build_cache::BuildCache,
+    models::{check_state::CheckState, exo::Exo},
+};
 
 use super::exo_check_result::ExoCheckResult;
 
@@ -15,6 +18,8 @@ pub(super) struct ExoStatusReport {
     pub(super) compilation_output: Vec<String>,
     pub(super) elf_path: PathBuf,
     pub(super) exo: Arc<Exo>,
+    /// Hash of the build inputs when the exo was last compiled, see `App::on_file_save`
+    pub(super) sources_hash: Option<String>,
 }
 
 impl ExoStatusReport {
@@ -31,9 +36,16 @@ impl ExoStatusReport {
             compilation_output: Vec::new(),
             elf_path,
             exo: Arc::new(exo.clone()),
+            sources_hash: ExoStatusReport::sources_hash(exo),
         }
     }
 
+    /// Hashes the inputs of the exo build: its files, the shared files and the compiler flags
+    pub(super) fn sources_hash(exo: &Exo) -> Option<String> {
+        let files: Vec<PathBuf> = exo.files.iter().chain(&exo.shared).cloned().collect();
+        BuildCache::hash(&files, &exo.flags)
+    }
+
     /// Helper function to get a `Vec<CheckState>` from check results
     /// Useful to send the check states to the Ui
     /// Check `UiState::CheckResults` for more information
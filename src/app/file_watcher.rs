@@ -1,17 +1,42 @@
-use log::error;
+use log::{error, info, warn};
 
-use super::app::App;
+use super::{app::App, exo_status_report::ExoStatusReport};
 
 /// Functions related to handling file saving events
 impl App {
     /// File saved event handler
     /// Called when one the current exo files gets saved
+    /// Editors often touch files without changing them, if the exo files, shared files and flags
+    /// didn't change since the last compilation, the current binary and check results are kept
     pub(super) fn on_file_save(&mut self) {
+        self.check_readonly_files();
+        if self.current_exo().reset_error.is_some() {
+            self.current_exo_mut().reset_error = None;
+            self.refresh_train_exo();
+        }
         if let Some(ref mut cr) = self.current_run {
-            let compile = App::compile(&self.work_handler, &cr.exo);
+            let sources_hash = ExoStatusReport::sources_hash(&cr.exo);
+            if sources_hash.is_some() && sources_hash == cr.sources_hash {
+                info!("Exo files didn't change, skipping compilation");
+                return;
+            }
+            cr.sources_hash = sources_hash;
+            let compile = App::compile(&self.work_handler, &self.toolchain, &cr.exo);
             if let Err(err) = compile {
                 error!("Error Starting Compilation {}", err);
             }
         }
     }
+
+    /// Warns on the train page when a read-only file of the current exo was modified
+    fn check_readonly_files(&mut self) {
+        let modified = self.current_exo().modified_readonly_files();
+        if modified != self.current_exo().modified_readonly {
+            if !modified.is_empty() {
+                warn!("Read-only files were modified: {:?}", modified);
+            }
+            self.current_exo_mut().modified_readonly = modified;
+            self.refresh_train_exo();
+        }
+    }
 }
@@ -0,0 +1,307 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Arc,
+    },
+};
+
+use console::style;
+
+use crate::{
+    core::{
+        compiler::toolchain::Toolchain,
+        file_utils::file_utils::{list_dir_folders, write_file},
+        work::{work::Work, work_handler::WorkHandler, work_type::WorkType},
+    },
+    models::{
+        check_state::CheckStatus,
+        event::Event,
+        exo::Exo,
+        grade::{ExoGrade, GradeStatus},
+    },
+};
+
+use super::{
+    app::App,
+    gradebook::{Gradebook, GradebookFormat},
+    headless::Headless,
+};
+
+/// Grades every exo of one student submission
+/// The submission folder mirrors the course: `<submission>/<skill>/<exo>/main.c` replaces the
+/// `main.c` of the exo, the other exo files are taken from the course
+pub(super) struct SubmissionGrader {
+    id: usize,
+    toolchain: Toolchain,
+    course_folder: PathBuf,
+    exos: Vec<Exo>,
+    submission: PathBuf,
+}
+
+impl SubmissionGrader {
+    pub(super) fn new(
+        id: usize,
+        toolchain: Toolchain,
+        course_folder: PathBuf,
+        exos: Vec<Exo>,
+        submission: PathBuf,
+    ) -> Self {
+        Self {
+            id,
+            toolchain,
+            course_folder,
+            exos,
+            submission,
+        }
+    }
+
+    /// Copies the exo files in `folder`, replaced by the ones of the submission
+    /// The read-only files are always taken from the course, so they can't be changed to pass
+    /// Returns None if the submission doesn't contain any editable file of this exo
+    fn copy_with_submission(
+        exo: &Exo,
+        submitted_folder: &Path,
+        folder: &Path,
+    ) -> std::io::Result<Option<Exo>> {
+        let mut files = Vec::new();
+        let mut submitted = false;
+        for file in &exo.files {
+            let name = file.file_name().unwrap_or_default();
+            let submitted_file = submitted_folder.join(name);
+            let source = if submitted_file.is_file() && !exo.readonly.contains(file) {
+                submitted = true;
+                submitted_file
+            } else {
+                file.clone()
+            };
+            let target = folder.join(name);
+            std::fs::copy(source, &target)?;
+            files.push(target);
+        }
+        if !submitted {
+            return Ok(None);
+        }
+        let mut submitted_exo = exo.clone();
+        submitted_exo.folder = PathBuf::from(folder);
+        submitted_exo.files = files;
+        Ok(Some(submitted_exo))
+    }
+
+    /// Builds and checks the submitted version of `exo` inside `folder`
+    /// Setting `stop` interrupts the checks, like when the grading is cancelled
+    fn grade_exo(&self, exo: &Exo, folder: &Path, stop: Arc<AtomicBool>) -> ExoGrade {
+        let relative_folder = exo
+            .folder
+            .strip_prefix(&self.course_folder)
+            .unwrap_or(&exo.folder)
+            .to_path_buf();
+        let mut grade = ExoGrade {
+            folder: relative_folder.clone(),
+            status: GradeStatus::Missing,
+            passed: 0,
+            total: exo.checks.len(),
+        };
+        let exo_folder = folder.join(&relative_folder);
+        let build_folder = exo_folder.join("build");
+        let submitted_exo = std::fs::create_dir_all(&build_folder).and_then(|_| {
+            SubmissionGrader::copy_with_submission(
+                exo,
+                &self.submission.join(&relative_folder),
+                &exo_folder,
+            )
+        });
+        let Ok(Some(submitted_exo)) = submitted_exo else {
+            return grade;
+        };
+        let run = Headless::run_prepared_exo(
+            &submitted_exo,
+            App::prepare_compilation_in(&self.toolchain, &submitted_exo, &build_folder),
+            stop,
+        );
+        if run.compilation_error.is_some() {
+            grade.status = GradeStatus::CompilationFailed;
+            return grade;
+        }
+        grade.status = GradeStatus::Graded;
+        grade.passed = run
+            .report
+            .check_results
+            .iter()
+            .filter(|result| result.state.status == CheckStatus::Passed)
+            .count();
+        grade
+    }
+}
+
+impl Work for SubmissionGrader {
+    fn run(&self, tx: Sender<Event>, stop: Arc<AtomicBool>) -> bool {
+        let Ok(folder) = tempfile::tempdir() else {
+            let _ = tx.send(Event::SubmissionGraded(self.id, vec![]));
+            return false;
+        };
+        let mut grades = Vec::new();
+        for exo in &self.exos {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            grades.push(self.grade_exo(exo, folder.path(), stop.clone()));
+        }
+        let _ = tx.send(Event::SubmissionGraded(self.id, grades));
+        true
+    }
+
+    fn work_type(&self) -> WorkType {
+        WorkType::Grader
+    }
+}
+
+/// Functions related to grading the submissions of students
+impl Headless {
+    /// Grades every submission found in `submissions`, one folder per student, against the exos
+    /// inside the target folder. At most `jobs` submissions are graded at the same time
+    /// The gradebook is written to `output`, or to stdout when no file is given
+    /// Returns false if the submissions couldn't be graded, failing checks don't count
+    pub fn grade(
+        &self,
+        submissions: &PathBuf,
+        jobs: usize,
+        format: GradebookFormat,
+        output: Option<&PathBuf>,
+    ) -> bool {
+        let exos: Vec<Exo> = self.target_exos().into_iter().cloned().collect();
+        if exos.is_empty() {
+            eprintln!(
+                "{} no exo found in {:?}",
+                style("error:").red(),
+                self.target
+            );
+            return false;
+        }
+        let mut students = match list_dir_folders(submissions) {
+            Ok(students) => students,
+            Err(err) => {
+                eprintln!(
+                    "{} couldn't read the submissions in {:?} ({})",
+                    style("error:").red(),
+                    submissions,
+                    err
+                );
+                return false;
+            }
+        };
+        students.sort();
+
+        let (tx, rx) = mpsc::channel();
+        let work_handler = WorkHandler::new(tx);
+        let mut grades: Vec<Option<Vec<ExoGrade>>> = vec![None; students.len()];
+        let mut running = 0;
+        let mut next = 0;
+        while next < students.len() || running > 0 {
+            // Fill the pool, then wait for a submission to be graded before starting the next one
+            if next < students.len() && running < jobs.max(1) {
+                let grader = SubmissionGrader::new(
+                    next,
+                    self.toolchain.clone(),
+                    self.project.folder.clone(),
+                    exos.clone(),
+                    students[next].clone(),
+                );
+                if let Ok(mut handler) = work_handler.lock() {
+                    handler.spawn_worker(Box::new(grader));
+                }
+                running += 1;
+                next += 1;
+                continue;
+            }
+            let Ok(event) = rx.recv() else {
+                break;
+            };
+            if let Event::SubmissionGraded(id, exo_grades) = event {
+                running -= 1;
+                eprintln!(
+                    "Graded {} ({}/{})",
+                    Gradebook::student_name(&students[id]),
+                    grades.iter().flatten().count() + 1,
+                    students.len()
+                );
+                grades[id] = Some(exo_grades);
+            }
+        }
+        if let Ok(mut handler) = work_handler.lock() {
+            handler.stop_all_workers_and_wait();
+        }
+
+        let gradebook = Gradebook::new(
+            &exos,
+            &self.project.folder,
+            students
+                .iter()
+                .zip(grades)
+                .map(|(student, grades)| (student.clone(), grades.unwrap_or_default()))
+                .collect(),
+        );
+        let content = gradebook.render(format);
+        match output {
+            Some(file) => {
+                if let Err(err) = write_file(file, &content) {
+                    eprintln!(
+                        "{} couldn't write the gradebook to {:?} ({})",
+                        style("error:").red(),
+                        file,
+                        err
+                    );
+                    return false;
+                }
+                println!(
+                    "Gradebook of {} submissions written to {:?}",
+                    students.len(),
+                    file
+                );
+            }
+            None => print!("{}", content),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::parser::from_dir::FromDir;
+
+    use super::*;
+
+    #[test]
+    fn submitted_files_replace_exo_files() {
+        let exo_folder = PathBuf::from("examples/mock-plx-project/mock-skill/readonly-files");
+        let (exo, _) = Exo::from_dir(&exo_folder).unwrap();
+        let submissions = PathBuf::from("examples/mock-plx-project/mock-submissions/alice");
+        let folder = tempfile::tempdir().unwrap();
+
+        let copy = SubmissionGrader::copy_with_submission(
+            &exo,
+            &submissions.join("intro").join("basic-args"),
+            folder.path(),
+        );
+        assert_eq!(copy.unwrap(), None);
+
+        let submitted_folder = submissions.join("mock-skill").join("readonly-files");
+        let copy = SubmissionGrader::copy_with_submission(&exo, &submitted_folder, folder.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            copy.files,
+            vec![folder.path().join("queue.c"), folder.path().join("main.c")]
+        );
+        assert_eq!(
+            std::fs::read(folder.path().join("queue.c")).unwrap(),
+            std::fs::read(submitted_folder.join("queue.c")).unwrap()
+        );
+        // The submitted read-only file is ignored
+        assert_eq!(
+            std::fs::read(folder.path().join("main.c")).unwrap(),
+            std::fs::read(exo_folder.join("main.c")).unwrap()
+        );
+    }
+}
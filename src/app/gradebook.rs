@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::models::{exo::Exo, grade::ExoGrade};
+
+/// Formats in which the gradebook of `plx grade` can be written
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradebookFormat {
+    /// One line per student and one column per exo with its score
+    Csv,
+    /// Status and number of passed checks of every exo of every student
+    Json,
+}
+
+/// Exo graded in the gradebook
+#[derive(Serialize)]
+struct GradedExo {
+    name: String,
+    folder: PathBuf,
+    checks: usize,
+}
+
+/// Grades of one student
+#[derive(Serialize)]
+struct Submission {
+    student: String,
+    /// Mean of the exo scores, between 0 and 1
+    score: f64,
+    exos: Vec<ExoGrade>,
+}
+
+/// Scores of every student for every exo
+#[derive(Serialize)]
+pub(super) struct Gradebook {
+    exos: Vec<GradedExo>,
+    submissions: Vec<Submission>,
+}
+
+impl Gradebook {
+    /// Builds the gradebook from the grades of each submission folder
+    /// The grades of a submission are in the same order as `exos`
+    pub(super) fn new(
+        exos: &[Exo],
+        course_folder: &Path,
+        grades: Vec<(PathBuf, Vec<ExoGrade>)>,
+    ) -> Self {
+        let exos = exos
+            .iter()
+            .map(|exo| GradedExo {
+                name: exo.name.clone(),
+                folder: exo
+                    .folder
+                    .strip_prefix(course_folder)
+                    .unwrap_or(&exo.folder)
+                    .to_path_buf(),
+                checks: exo.checks.len(),
+            })
+            .collect::<Vec<_>>();
+        let submissions = grades
+            .into_iter()
+            .map(|(folder, grades)| Submission {
+                student: Gradebook::student_name(&folder),
+                score: grades.iter().map(ExoGrade::score).sum::<f64>() / exos.len().max(1) as f64,
+                exos: grades,
+            })
+            .collect();
+        Self { exos, submissions }
+    }
+
+    /// Returns the name of the student from its submission folder
+    pub(super) fn student_name(folder: &Path) -> String {
+        folder
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    pub(super) fn render(&self, format: GradebookFormat) -> String {
+        match format {
+            GradebookFormat::Csv => self.to_csv(),
+            // The gradebook only contains strings and numbers, it can't fail to serialize
+            GradebookFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default() + "\n",
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut header = vec![String::from("student")];
+        header.extend(
+            self.exos
+                .iter()
+                .map(|exo| csv_field(&exo.folder.display().to_string())),
+        );
+        header.push(String::from("score"));
+        let mut csv = header.join(",") + "\n";
+        for submission in &self.submissions {
+            let mut line = vec![csv_field(&submission.student)];
+            line.extend(
+                submission
+                    .exos
+                    .iter()
+                    .map(|grade| format!("{:.2}", grade.score())),
+            );
+            line.push(format!("{:.2}", submission.score));
+            csv.push_str(&(line.join(",") + "\n"));
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a new line
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::grade::GradeStatus;
+
+    use super::*;
+
+    fn grade(folder: &str, status: GradeStatus, passed: usize) -> ExoGrade {
+        ExoGrade {
+            folder: PathBuf::from(folder),
+            status,
+            passed,
+            total: 4,
+        }
+    }
+
+    #[test]
+    fn csv_has_one_line_per_student() {
+        let gradebook = Gradebook {
+            exos: vec![
+                GradedExo {
+                    name: String::from("A"),
+                    folder: PathBuf::from("intro/a"),
+                    checks: 4,
+                },
+                GradedExo {
+                    name: String::from("B"),
+                    folder: PathBuf::from("intro/b,c"),
+                    checks: 4,
+                },
+            ],
+            submissions: vec![Submission {
+                student: String::from("alice"),
+                score: 0.5,
+                exos: vec![
+                    grade("intro/a", GradeStatus::Graded, 4),
+                    grade("intro/b,c", GradeStatus::Missing, 0),
+                ],
+            }],
+        };
+        assert_eq!(
+            gradebook.render(GradebookFormat::Csv),
+            "student,intro/a,\"intro/b,c\",score\nalice,1.00,0.00,0.50\n"
+        );
+    }
+
+    #[test]
+    fn score_is_the_mean_of_the_exos() {
+        let gradebook = Gradebook::new(
+            &[],
+            Path::new("/course"),
+            vec![(
+                PathBuf::from("/submissions/bob"),
+                vec![grade("a", GradeStatus::Graded, 1)],
+            )],
+        );
+        assert_eq!(gradebook.submissions[0].student, "bob");
+        assert_eq!(gradebook.submissions[0].score, 0.25);
+    }
+}
@@ -0,0 +1,720 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use console::style;
+use serde::Serialize;
+
+use crate::{
+    core::{
+        check::checker::Checker,
+        compiler::{compile_runner::CompileRunner, toolchain::Toolchain},
+        core_error::CoreInitError,
+        file_utils::{
+            file_parser::ParseWarning,
+            file_utils::{current_folder, write_file},
+        },
+        launcher::launcher::Launcher,
+        parser::from_dir::FromDir,
+        work::work::Work,
+    },
+    models::{
+        check::CheckTest, check_state::CheckStatus, constants::COURSE_INFO_FILE, event::Event,
+        exo::Exo, exo_metadata::ExoFilter, exo_state::ExoState, project::Project, skill::Skill,
+        user_config::UserConfig,
+    },
+};
+
+use super::{
+    app::App, errors::CompilationStartError, exo_status_report::ExoStatusReport,
+    report::ReportFormat,
+};
+
+/// Time after which a check is stopped when the exo doesn't set a `timeout`, so a submission
+/// looping forever can't block `plx check` or `plx grade`
+const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checks exos without the UI, useful in CI or to run checks from a terminal
+/// It reuses the same workers as the TUI (`CompileRunner`, `Launcher`, `Checker`) but runs them
+/// one after the other in the current thread
+pub struct Headless {
+    pub(super) project: Project,
+    pub(super) toolchain: Toolchain,
+    /// Absolute path of the course, skill or exo targeted by the command
+    pub(super) target: PathBuf,
+    /// Problems found while parsing the course
+    warnings: Vec<ParseWarning>,
+    /// Only the exos matching it are used by the commands
+    filter: ExoFilter,
+    /// Folder where the exos are built in `<skill>/<exo>`, None uses the build folder of the course
+    build_root: Option<PathBuf>,
+}
+
+/// Result of the headless run of one exo
+pub(super) struct HeadlessRun {
+    pub(super) report: ExoStatusReport,
+    /// None if the compilation succeeded, the reason of the failure otherwise
+    pub(super) compilation_error: Option<String>,
+    /// Time taken to compile the exo and run all its checks
+    pub(super) duration: Duration,
+}
+
+impl HeadlessRun {
+    /// Returns true if the exo compiled and every check passed
+    pub(super) fn passed(&self) -> bool {
+        self.compilation_error.is_none()
+            && App::all_checks_passed(&self.report.to_vec_check_state())
+    }
+}
+
+/// Returns `part` as a percentage of `total`, rounded down
+fn percentage(part: usize, total: usize) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    part * 100 / total
+}
+
+/// Progress of the course printed by `plx list --json`
+#[derive(Serialize)]
+struct CourseProgress<'a> {
+    course: &'a str,
+    done: usize,
+    total: usize,
+    completion: usize,
+    skills: Vec<SkillProgress<'a>>,
+}
+
+#[derive(Serialize)]
+struct SkillProgress<'a> {
+    name: &'a str,
+    done: usize,
+    total: usize,
+    completion: usize,
+    exos: Vec<ExoProgress<'a>>,
+}
+
+#[derive(Serialize)]
+struct ExoProgress<'a> {
+    name: &'a str,
+    folder: PathBuf,
+    state: &'a ExoState,
+    favorite: bool,
+    hints_used: usize,
+    /// Why the exo can't be started yet
+    locked: Option<&'a str>,
+}
+
+impl<'a> CourseProgress<'a> {
+    /// Progress of the skills and exos selected by `filter`
+    fn new(project: &'a Project, skills: &[(&'a Skill, Vec<&'a Exo>)]) -> Self {
+        let skills: Vec<SkillProgress> = skills
+            .iter()
+            .map(|(skill, exos)| {
+                let done = done_exos(exos);
+                SkillProgress {
+                    name: &skill.name,
+                    done,
+                    total: exos.len(),
+                    completion: percentage(done, exos.len()),
+                    exos: exos
+                        .iter()
+                        .map(|exo| ExoProgress {
+                            name: &exo.name,
+                            folder: Headless::relative_folder(exo),
+                            state: &exo.state,
+                            favorite: exo.favorite,
+                            hints_used: exo.hints_used,
+                            locked: exo.locked.as_deref(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        let done = skills.iter().map(|skill| skill.done).sum();
+        let total = skills.iter().map(|skill| skill.total).sum();
+        Self {
+            course: &project.name,
+            done,
+            total,
+            completion: percentage(done, total),
+            skills,
+        }
+    }
+}
+
+/// Returns the number of exos done in `exos`
+fn done_exos(exos: &[&Exo]) -> usize {
+    exos.iter()
+        .filter(|exo| exo.state == ExoState::Done)
+        .count()
+}
+
+impl Headless {
+    /// Loads the course containing `path`, it can be the course folder, a skill or an exo
+    /// The current folder is changed to the course root, so build folders are generated there
+    pub fn new(path: &Path) -> Result<Self, CoreInitError> {
+        let target = path
+            .canonicalize()
+            .map_err(|_| CoreInitError::PlxProjNotFound)?;
+        let course_folder = Headless::find_course_folder(&target)?;
+        std::env::set_current_dir(&course_folder).map_err(|_| CoreInitError::PlxProjNotFound)?;
+        let (project, warnings) = Project::from_dir(&course_folder)
+            .map_err(|(err, _)| CoreInitError::ProjFilesParsingError(format!("{:?}", err)))?;
+        let config = UserConfig::load().unwrap_or_else(|err| {
+            eprintln!("{} {}", style("warning:").yellow(), err);
+            UserConfig::default()
+        });
+        Ok(Self {
+            project,
+            toolchain: config.toolchain,
+            target,
+            warnings,
+            filter: ExoFilter::default(),
+            build_root: None,
+        })
+    }
+
+    /// Restricts the commands to the exos matching `filter`
+    pub fn with_filter(mut self, filter: ExoFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    fn print_warnings(&self) {
+        self.warnings
+            .iter()
+            .for_each(|warning| eprintln!("{} {}", style("warning:").yellow(), warning));
+    }
+
+    /// Finds the closest folder containing a course.toml, starting from `path`
+    fn find_course_folder(path: &Path) -> Result<PathBuf, CoreInitError> {
+        let path = path
+            .canonicalize()
+            .map_err(|_| CoreInitError::PlxProjNotFound)?;
+        path.ancestors()
+            .find(|folder| folder.join(COURSE_INFO_FILE).exists())
+            .map(Path::to_path_buf)
+            .ok_or(CoreInitError::PlxProjNotFound)
+    }
+
+    /// Returns every exo located inside the target folder and matching the filter, in the course
+    /// order
+    pub(super) fn target_exos(&self) -> Vec<&Exo> {
+        self.project
+            .skills
+            .iter()
+            .flat_map(|skill| skill.exos.iter())
+            .filter(|exo| {
+                exo.folder
+                    .canonicalize()
+                    .is_ok_and(|folder| folder.starts_with(&self.target))
+                    && self.filter.matches(&exo.metadata)
+            })
+            .collect()
+    }
+
+    /// Returns the skills with their exos matching the filter, skills without any matching exo
+    /// are skipped unless the filter is empty
+    fn filtered_skills(&self) -> Vec<(&Skill, Vec<&Exo>)> {
+        self.project
+            .skills
+            .iter()
+            .map(|skill| {
+                let exos = skill
+                    .exos
+                    .iter()
+                    .filter(|exo| self.filter.matches(&exo.metadata))
+                    .collect::<Vec<_>>();
+                (skill, exos)
+            })
+            .filter(|(_, exos)| self.filter.is_empty() || !exos.is_empty())
+            .collect()
+    }
+
+    /// Checks every exo inside the target folder and prints a summary
+    /// With a `report` format, the results are also written to `output`, or to stdout instead of
+    /// the summary when no output file is given
+    /// Returns true if every exo compiled and passed all its checks
+    pub fn check(&self, report: Option<ReportFormat>, output: Option<&PathBuf>) -> bool {
+        self.print_warnings();
+        let exos = self.target_exos();
+        if exos.is_empty() {
+            let filtered = if self.filter.is_empty() {
+                ""
+            } else {
+                " matching the filter"
+            };
+            eprintln!(
+                "{} no exo found in {:?}{}",
+                style("error:").red(),
+                self.target,
+                filtered
+            );
+            return false;
+        }
+        let mut missing = self.toolchain.find_missing(
+            &exos
+                .iter()
+                .filter_map(|exo| exo.compiler())
+                .collect::<Vec<_>>(),
+        );
+        missing
+            .drain(..)
+            .for_each(|err| eprintln!("{} {}", style("error:").red(), err));
+        exos.iter()
+            .flat_map(|exo| exo.modified_readonly.iter())
+            .for_each(|file| {
+                eprintln!(
+                    "{} read-only file {:?} was modified",
+                    style("warning:").yellow(),
+                    file
+                )
+            });
+
+        // The report replaces the summary when it's written to stdout
+        let print_summary = report.is_none() || output.is_some();
+        let runs: Vec<HeadlessRun> = exos
+            .iter()
+            .map(|exo| self.run_exo(exo))
+            .inspect(|run| {
+                if print_summary {
+                    Headless::print_run(run)
+                }
+            })
+            .collect();
+        let passed_count = runs.iter().filter(|run| run.passed()).count();
+
+        if let Some(format) = report {
+            let content = format.render(&self.project.name, &runs);
+            match output {
+                Some(file) => {
+                    if let Err(err) = write_file(file, &content) {
+                        eprintln!(
+                            "{} couldn't write the report to {:?} ({})",
+                            style("error:").red(),
+                            file,
+                            err
+                        );
+                        return false;
+                    }
+                }
+                None => print!("{}", content),
+            }
+        }
+        if !print_summary {
+            return passed_count == exos.len();
+        }
+
+        let summary = format!("{}/{} exos passed", passed_count, exos.len());
+        if passed_count == exos.len() {
+            println!("\n{}", style(summary).green().bold());
+            true
+        } else {
+            println!("\n{}", style(summary).red().bold());
+            false
+        }
+    }
+
+    /// Prints the skills and exos of the course with their state, favorites and completion
+    /// With `json`, the same information is printed as JSON for scripts
+    pub fn list(&self, json: bool) -> bool {
+        self.print_warnings();
+        let skills = self.filtered_skills();
+        if json {
+            // The progress only contains strings and numbers, it can't fail to serialize
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&CourseProgress::new(&self.project, &skills))
+                    .unwrap_or_default()
+            );
+            return true;
+        }
+        let total: usize = skills.iter().map(|(_, exos)| exos.len()).sum();
+        let done: usize = skills.iter().map(|(_, exos)| done_exos(exos)).sum();
+        println!(
+            "{}  {}",
+            style(&self.project.name).bold(),
+            style(format!(
+                "{}/{} exos done ({}%)",
+                done,
+                total,
+                percentage(done, total)
+            ))
+            .dim()
+        );
+        for (skill, exos) in skills.iter() {
+            let done = done_exos(exos);
+            println!(
+                "\n{}  {}",
+                style(&skill.name).bold(),
+                style(format!(
+                    "{}/{} ({}%)",
+                    done,
+                    exos.len(),
+                    percentage(done, exos.len())
+                ))
+                .dim()
+            );
+            for exo in exos.iter() {
+                let state = match exo.state {
+                    ExoState::Done => style("✓").green(),
+                    ExoState::InProgress => style("~").yellow(),
+                    ExoState::Todo => style("·").dim(),
+                };
+                let favorite = if exo.favorite { " ★" } else { "" };
+                let hints = if exo.hints_used > 0 {
+                    style(format!(" ({}/{} hints)", exo.hints_used, exo.hints.len())).dim()
+                } else {
+                    style(String::new())
+                };
+                let locked = match &exo.locked {
+                    Some(reason) => style(format!(" (locked: {})", reason)).dim(),
+                    None => style(String::new()),
+                };
+                println!("  {} {}{}{}{}", state, exo.name, favorite, hints, locked);
+            }
+        }
+        true
+    }
+
+    /// Parses the course containing `path` and prints every problem found in its files
+    /// Returns false if there is at least one error: the course, a skill or an exo that couldn't
+    /// be parsed. Warnings alone don't make the validation fail
+    pub fn validate(path: &Path) -> bool {
+        let course_folder = match Headless::find_course_folder(path) {
+            Ok(folder) => folder,
+            Err(err) => {
+                eprintln!("{} {}", style("error:").red(), err);
+                return false;
+            }
+        };
+        let (project, warnings, parse_error) = match Project::from_dir(&course_folder) {
+            Ok((project, warnings)) => (Some(project), warnings, None),
+            Err((err, warnings)) => (None, warnings, Some(err)),
+        };
+        let (errors, warnings): (Vec<&ParseWarning>, Vec<&ParseWarning>) =
+            warnings.iter().partition(|warning| warning.is_error());
+        errors
+            .iter()
+            .for_each(|err| println!("{} {}", style("error:").red().bold(), err));
+        warnings
+            .iter()
+            .for_each(|warning| println!("{} {}", style("warning:").yellow().bold(), warning));
+        if let Some(err) = &parse_error {
+            println!("{} {}", style("error:").red().bold(), err);
+        }
+
+        let errors_count = errors.len() + parse_error.iter().count();
+        let summary = format!("{} errors, {} warnings", errors_count, warnings.len());
+        match project {
+            Some(project) if errors_count == 0 => {
+                let exos_count: usize = project.skills.iter().map(|skill| skill.exos.len()).sum();
+                println!(
+                    "\n{} ({} skills, {} exos)",
+                    style(summary).green().bold(),
+                    project.skills.len(),
+                    exos_count
+                );
+                true
+            }
+            _ => {
+                println!("\n{}", style(summary).red().bold());
+                false
+            }
+        }
+    }
+
+    /// Restores the original files and resets the progress of every exo inside the target folder
+    pub fn reset(&self) -> bool {
+        let exos = self.target_exos();
+        if exos.is_empty() {
+            eprintln!(
+                "{} no exo found in {:?}",
+                style("error:").red(),
+                self.target
+            );
+            return false;
+        }
+        let mut success = true;
+        for exo in &exos {
+            match App::reset_exo(exo) {
+                Ok(true) => println!("Reset {}", exo.name),
+                Ok(false) => println!(
+                    "Skipped {} {}",
+                    exo.name,
+                    style("(never opened, its original files weren't saved so nothing was reset)")
+                        .dim()
+                ),
+                Err(err) => {
+                    println!(
+                        "{} couldn't restore the files of {} ({})",
+                        style("error:").red(),
+                        exo.name,
+                        err
+                    );
+                    success = false;
+                }
+            }
+        }
+        success
+    }
+
+    /// Compiles `exo` in its build folder, runs its checks and collects the results
+    pub(super) fn run_exo(&self, exo: &Exo) -> HeadlessRun {
+        let Some(root) = &self.build_root else {
+            return Headless::run_prepared_exo(
+                exo,
+                App::prepare_compilation(&self.toolchain, exo),
+                Arc::new(AtomicBool::new(false)),
+            );
+        };
+        let build_folder = match (exo.folder.parent(), exo.folder.file_name()) {
+            (Some(skill), Some(folder)) => root
+                .join(skill.file_name().unwrap_or_default())
+                .join(folder),
+            _ => root.clone(),
+        };
+        if std::fs::create_dir_all(&build_folder).is_err() {
+            return Headless::run_prepared_exo(
+                exo,
+                Err(CompilationStartError::BuildFolderGenerationFailed),
+                Arc::new(AtomicBool::new(false)),
+            );
+        }
+        self.run_exo_in(exo, &build_folder)
+    }
+
+    /// Compiles `exo` in `build_folder`, runs its checks and collects the results
+    pub(super) fn run_exo_in(&self, exo: &Exo, build_folder: &Path) -> HeadlessRun {
+        Headless::run_prepared_exo(
+            exo,
+            App::prepare_compilation_in(&self.toolchain, exo, build_folder),
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
+
+    /// Runs the compilation prepared by `App::prepare_compilation`, then the checks of `exo`
+    /// Setting `stop` kills the running process and skips the remaining checks
+    pub(super) fn run_prepared_exo(
+        exo: &Exo,
+        prepared: Result<(CompileRunner, PathBuf), CompilationStartError>,
+        stop: Arc<AtomicBool>,
+    ) -> HeadlessRun {
+        let (tx, rx) = mpsc::channel();
+        let start = Instant::now();
+
+        let (runner, output_path) = match prepared {
+            Ok(prepared) => prepared,
+            Err(err) => {
+                return HeadlessRun {
+                    report: ExoStatusReport::new(exo, PathBuf::new()),
+                    compilation_error: Some(err.to_string()),
+                    duration: start.elapsed(),
+                }
+            }
+        };
+        let mut report = ExoStatusReport::new(exo, output_path);
+        runner.run(tx.clone(), stop.clone());
+        let mut compiled = false;
+        for event in rx.try_iter() {
+            match event {
+                Event::CompilationOutputLine(line) => report.compilation_output.push(line),
+                Event::CompilationEnd(success) => compiled = success,
+                _ => {}
+            }
+        }
+        if !compiled {
+            return HeadlessRun {
+                report,
+                compilation_error: Some(String::from("Compilation failed")),
+                duration: start.elapsed(),
+            };
+        }
+
+        for id in 0..report.check_results.len() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let check = Arc::clone(&report.check_results[id].state.check);
+            let Some(launcher) = Launcher::new(id, report.elf_path.clone(), check.args.clone())
+                .map(|launcher| {
+                    launcher
+                        .with_timeout(exo.timeout.or(Some(DEFAULT_CHECK_TIMEOUT)))
+                        .with_stdin(check.stdin.clone())
+                })
+            else {
+                continue;
+            };
+            let check_start = Instant::now();
+            launcher.run(tx.clone(), stop.clone());
+            Headless::collect_events(&rx, &mut report);
+            if let CheckStatus::RunFail(_) = report.check_results[id].state.status {
+                report.check_results[id].duration = Some(check_start.elapsed());
+                continue;
+            }
+            let output = report.check_results[id].output.join("\n");
+            Checker::new(id, check, output)
+                .with_comparison(exo.comparison)
+                .run(tx.clone(), stop.clone());
+            Headless::collect_events(&rx, &mut report);
+            report.check_results[id].duration = Some(check_start.elapsed());
+        }
+        HeadlessRun {
+            report,
+            compilation_error: None,
+            duration: start.elapsed(),
+        }
+    }
+
+    /// Applies the run and check events received so far to the report
+    fn collect_events(rx: &Receiver<Event>, report: &mut ExoStatusReport) {
+        for event in rx.try_iter() {
+            let (id, status) = match event {
+                Event::RunOutputLine(id, line) => {
+                    if let Some(result) = report.check_results.get_mut(id) {
+                        result.output.push(line);
+                    }
+                    continue;
+                }
+                Event::RunFail(id, err) => (id, CheckStatus::RunFail(err)),
+                Event::OutputCheckPassed(id) => (id, CheckStatus::Passed),
+                Event::OutputCheckFailed(id, diff) => {
+                    let Some(result) = report.check_results.get(id) else {
+                        continue;
+                    };
+                    let CheckTest::Output { expected } = &result.state.check.test;
+                    let output = result.output.join("\n");
+                    (id, CheckStatus::Failed(expected.clone(), output, diff))
+                }
+                _ => continue,
+            };
+            if let Some(result) = report.check_results.get_mut(id) {
+                result.state.status = status;
+            }
+        }
+    }
+
+    /// Returns the folder of the exo relative to the course root
+    pub(super) fn relative_folder(exo: &Exo) -> PathBuf {
+        // The current folder is the course root, see `Headless::new`
+        current_folder()
+            .ok()
+            .and_then(|course| exo.folder.strip_prefix(course).ok().map(Path::to_path_buf))
+            .unwrap_or(exo.folder.clone())
+    }
+
+    /// Prints the result of an exo and the details of what failed
+    fn print_run(run: &HeadlessRun) {
+        let exo = &run.report.exo;
+        let folder = Headless::relative_folder(exo);
+        let title = format!("{} ({})", exo.name, folder.display());
+        if run.passed() {
+            println!("{} {}", style("PASS").green().bold(), title);
+        } else {
+            println!("{} {}", style("FAIL").red().bold(), title);
+        }
+        if let Some(err) = &run.compilation_error {
+            println!("  {}", style(err).red());
+            run.report
+                .compilation_output
+                .iter()
+                .for_each(|line| println!("    {}", line));
+            return;
+        }
+        for result in &run.report.check_results {
+            let name = &result.state.check.name;
+            match &result.state.status {
+                CheckStatus::Passed => println!("  {} {}", style("✓").green(), name),
+                CheckStatus::Failed(_, _, diff) => {
+                    println!("  {} {}", style("✗").red(), name);
+                    diff.to_ansi_colors()
+                        .lines()
+                        .for_each(|line| println!("      {}", line));
+                }
+                CheckStatus::RunFail(err) => {
+                    println!("  {} {} (couldn't run: {})", style("✗").red(), name, err)
+                }
+                _ => println!("  {} {} (not checked)", style("✗").red(), name),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// Loads the mock course without changing the current folder, unlike `Headless::new`
+    /// The basic-output exo is solved by building its solution instead of its exo file
+    /// The exos are built in `build_root`
+    pub(in crate::app) fn mock_course(target: &str, build_root: &Path) -> Headless {
+        let (mut project, warnings) =
+            Project::from_dir(&PathBuf::from("examples/mock-plx-project")).unwrap();
+        let skills = Arc::make_mut(&mut project.skills);
+        let exo = &mut Arc::make_mut(&mut skills[0].exos)[1];
+        exo.files = exo.solutions.clone();
+        Headless {
+            project,
+            toolchain: Toolchain::default(),
+            target: Path::new(target).canonicalize().unwrap(),
+            warnings,
+            filter: ExoFilter::default(),
+            build_root: Some(build_root.to_path_buf()),
+        }
+    }
+
+    #[test]
+    fn course_folder_is_found_from_an_exo() {
+        let folder =
+            Headless::find_course_folder(Path::new("examples/mock-plx-project/intro/basic-args"))
+                .unwrap();
+        assert_eq!(
+            folder,
+            Path::new("examples/mock-plx-project")
+                .canonicalize()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_course_fails_validation() {
+        assert!(!Headless::validate(Path::new(
+            "examples/mock-plx-project/intro/basic-args"
+        )));
+    }
+
+    #[test]
+    fn check_counts_passed_exos() {
+        let report = tempfile::NamedTempFile::new().unwrap();
+        let output = report.path().to_path_buf();
+        let build = tempfile::tempdir().unwrap();
+        let passed = mock_course("examples/mock-plx-project/intro/basic-output", build.path())
+            .check(Some(ReportFormat::Json), Some(&output));
+        let failed = mock_course("examples/mock-plx-project/intro/basic-args", build.path())
+            .check(None, None);
+        let all = mock_course("examples/mock-plx-project/intro", build.path())
+            .check(Some(ReportFormat::Json), Some(&output));
+
+        assert!(passed);
+        assert!(!failed);
+        assert!(!all);
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(report["passed"], 1);
+        assert_eq!(report["total"], 2);
+        assert_eq!(report["exos"][0]["passed"], false);
+        assert_eq!(report["exos"][1]["passed"], true);
+    }
+
+    #[test]
+    fn no_course_folder_outside_a_course() {
+        assert!(Headless::find_course_folder(Path::new("src")).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use log::error;
+use log::{error, warn};
 
 use crate::models::{exo_state::ExoState, key::Key, project::Project, ui_state::UiState};
 
@@ -17,6 +17,8 @@ impl App {
             Key::L | Key::Enter => self.on_l(), // Currently L and Enter do the same thing
             Key::N => self.on_n(),
             Key::P => self.on_p(),
+            Key::X => self.on_x(),
+            Key::I => self.on_i(),
             // Key::E => {}
             Key::Esc => self.on_esc(),
             Key::Interrogation => self.on_interrogation(),
@@ -25,7 +27,7 @@ impl App {
 
     pub(super) fn on_r(&mut self) {
         match &self.ui_state {
-            UiState::Home => self.resume_last_exo(),
+            UiState::Home { .. } => self.resume_last_exo(),
             _ => (),
         }
     }
@@ -39,7 +41,9 @@ impl App {
 
     pub(super) fn on_esc(&mut self) {
         match &self.ui_state {
-            UiState::Help { last_state, .. } => self.set_ui_state(*last_state.clone()),
+            UiState::Help { last_state, .. } | UiState::ConfirmReset { last_state, .. } => {
+                self.set_ui_state(*last_state.clone())
+            }
             UiState::ExoPreview { .. } => self.go_to_exo_selection(),
             _ => {}
         }
@@ -71,7 +75,7 @@ impl App {
     }
     pub(super) fn on_h(&mut self) {
         match &self.ui_state {
-            UiState::SkillSelection { .. } => self.set_ui_state(UiState::Home),
+            UiState::SkillSelection { .. } => self.go_to_home(),
             UiState::ExoSelection {
                 skill_index,
                 skills,
@@ -95,6 +99,7 @@ impl App {
                 skills: skills.clone(),
             }),
             UiState::CompileError { .. } | UiState::CheckResults { .. } => self.go_to_exo_preview(),
+            UiState::ConfirmReset { last_state, .. } => self.set_ui_state(*last_state.clone()),
             UiState::ShowSolution { .. } => {
                 if let Some(cr) = &self.current_run {
                     self.go_to_check_results(0, cr.to_vec_check_state())
@@ -110,28 +115,79 @@ impl App {
             UiState::ExoSelection { .. } => self.go_to_exo_preview(),
 
             //TODO refactor this code (duplicate)
-            UiState::ExoPreview { exo, .. } => match App::start_exo(&self.work_handler, exo) {
-                Ok(cr) => {
-                    self.current_run = Some(cr);
-                    self.go_to_compiling();
-                }
-                Err(err) =>
-                //TODO send this to the ui
-                {
-                    error!("Could not launch exo {}", err);
+            // Locked exos can be previewed but not started
+            UiState::ExoPreview { exo, .. } if exo.locked.is_none() => {
+                match App::start_exo(&self.work_handler, &self.toolchain, exo) {
+                    Ok(cr) => {
+                        self.current_run = Some(cr);
+                        self.go_to_compiling();
+                    }
+                    Err(err) =>
+                    //TODO send this to the ui
+                    {
+                        error!("Could not launch exo {}", err);
+                    }
                 }
-            },
-            UiState::CheckResults { checks, exo, .. } => {
+            }
+            UiState::CheckResults { checks, .. } => {
                 if App::all_checks_passed(checks) {
-                    Project::set_exo_state(exo, ExoState::Done);
-                    self.go_to_solution(0, 0);
+                    self.finish_exo();
+                }
+            }
+            UiState::CompileError {
+                diagnostics,
+                diagnostic_idx,
+                ..
+            } => {
+                if let Some(diagnostic) = diagnostics.get(*diagnostic_idx) {
+                    if App::open_editor_at(&self.work_handler, diagnostic).is_none() {
+                        error!("Could not open editor at {:?}", diagnostic.file);
+                    }
+                }
+            }
+            UiState::ConfirmReset { exo, last_state } => {
+                // The progress is only reset with the files, so the exo matches what is shown
+                let reset_error = match exo.restore_original_files() {
+                    Ok(true) => {
+                        Project::reset_exo_state(exo);
+                        None
+                    }
+                    Ok(false) => {
+                        warn!("No original files saved for {:?}", exo.folder);
+                        Some(String::from(
+                            "The original files were never saved, nothing was reset",
+                        ))
+                    }
+                    Err(err) => {
+                        error!("Couldn't reset {:?}: {}", exo.folder, err);
+                        Some(format!("Couldn't reset the exo ({})", err))
+                    }
+                };
+                let last_state = *last_state.clone();
+                let failed = reset_error.is_some();
+                let exo = self.current_exo_mut();
+                exo.reset_error = reset_error;
+                if !failed {
+                    exo.state = ExoState::Todo;
+                    exo.modified_readonly.clear();
+                    self.project.refresh_locks();
+                }
+                self.set_ui_state(last_state);
+                self.refresh_train_exo();
+                if !failed {
+                    // Recompile the restored files now instead of waiting for the watcher
+                    self.on_file_save();
                 }
             }
             UiState::ShowSolution { .. } => {
                 self.next_exo(true);
+                if self.current_exo().locked.is_some() {
+                    self.go_to_exo_preview();
+                    return;
+                }
 
                 //TODO refactor this code (duplicate)
-                match App::start_exo(&self.work_handler, self.current_exo()) {
+                match App::start_exo(&self.work_handler, &self.toolchain, self.current_exo()) {
                     Ok(cr) => {
                         self.current_run = Some(cr);
                         self.go_to_compiling();
@@ -158,11 +214,11 @@ impl App {
                     self.go_to_solution(*scroll_offset, solution_idx);
                 }
             }
+            UiState::CompileError { .. } => self.prev_diagnostic(),
 
-            UiState::CheckResults { checks, exo, .. } => {
+            UiState::CheckResults { checks, .. } => {
                 if App::all_checks_passed(checks) {
-                    Project::set_exo_state(exo, ExoState::Done);
-                    self.go_to_solution(0, 0);
+                    self.finish_exo();
                 }
             }
             _ => {}
@@ -181,16 +237,36 @@ impl App {
                     self.go_to_solution(*scroll_offset, solution_idx);
                 }
             }
+            UiState::CompileError { .. } => self.next_diagnostic(),
 
-            UiState::CheckResults { checks, exo, .. } => {
+            UiState::CheckResults { checks, .. } => {
                 if App::all_checks_passed(checks) {
-                    Project::set_exo_state(exo, ExoState::Done);
-                    self.go_to_solution(0, 0);
+                    self.finish_exo();
                 }
             }
             _ => {}
         }
     }
+    /// Asks for a confirmation before resetting the exo from the train page
+    pub(super) fn on_x(&mut self) {
+        match &self.ui_state {
+            UiState::Compiling { .. }
+            | UiState::CompileError { .. }
+            | UiState::CheckResults { .. } => {
+                self.go_to_reset_confirmation(Box::new(self.ui_state.clone()))
+            }
+            _ => {}
+        }
+    }
+    /// Reveals the next hint on the train page
+    pub(super) fn on_i(&mut self) {
+        match &self.ui_state {
+            UiState::Compiling { .. }
+            | UiState::CompileError { .. }
+            | UiState::CheckResults { .. } => self.show_next_hint(),
+            _ => {}
+        }
+    }
     ///
     /// Handles the '?' key press
     pub(super) fn on_interrogation(&mut self) {
@@ -33,6 +33,45 @@ impl App {
     }
 }
 
+//Compilation diagnostics
+impl App {
+    pub(super) fn prev_diagnostic(&mut self) {
+        if let UiState::CompileError { diagnostic_idx, .. } = self.ui_state {
+            if diagnostic_idx > 0 {
+                self.select_diagnostic(diagnostic_idx - 1);
+            }
+        }
+    }
+    pub(super) fn next_diagnostic(&mut self) {
+        if let UiState::CompileError {
+            diagnostic_idx,
+            ref diagnostics,
+            ..
+        } = self.ui_state
+        {
+            if diagnostic_idx + 1 < diagnostics.len() {
+                self.select_diagnostic(diagnostic_idx + 1);
+            }
+        }
+    }
+    fn select_diagnostic(&mut self, diagnostic_idx: usize) {
+        if let UiState::CompileError {
+            scroll_offset,
+            error,
+            diagnostics,
+            ..
+        } = &self.ui_state
+        {
+            self.go_to_compilation_error(
+                *scroll_offset,
+                error.to_string(),
+                diagnostics.clone(),
+                diagnostic_idx,
+            );
+        }
+    }
+}
+
 //Scroll
 impl App {
     pub(super) fn scroll_up(&mut self, scroll_offset: usize) {
@@ -46,9 +85,17 @@ impl App {
     pub(super) fn set_scroll_offset(&mut self, scroll_offset: usize) {
         match &self.ui_state {
             UiState::Help { last_state, .. } => self.go_to_help(last_state.clone(), scroll_offset),
-            UiState::CompileError { error, .. } => {
-                self.go_to_compilation_error(scroll_offset, error.to_string())
-            }
+            UiState::CompileError {
+                error,
+                diagnostics,
+                diagnostic_idx,
+                ..
+            } => self.go_to_compilation_error(
+                scroll_offset,
+                error.to_string(),
+                diagnostics.clone(),
+                *diagnostic_idx,
+            ),
             UiState::CheckResults { checks, .. } => {
                 self.go_to_check_results(scroll_offset, checks.clone())
             }
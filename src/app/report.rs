@@ -0,0 +1,465 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::models::check_state::CheckStatus;
+
+use super::{
+    exo_check_result::ExoCheckResult,
+    headless::{Headless, HeadlessRun},
+};
+
+/// Machine-readable formats in which the results of `plx check` can be written
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// JSON document with the status, duration, output and diff of every check
+    Json,
+    /// JUnit XML, understood by GitLab and GitHub test reports
+    Junit,
+    /// Test Anything Protocol, version 13
+    Tap,
+}
+
+impl ReportFormat {
+    /// Builds the report of the runs of the course `course_name` in this format
+    pub(super) fn render(&self, course_name: &str, runs: &[HeadlessRun]) -> String {
+        match self {
+            ReportFormat::Json => json_report(course_name, runs),
+            ReportFormat::Junit => junit_report(course_name, runs),
+            ReportFormat::Tap => tap_report(runs),
+        }
+    }
+}
+
+/// Name of the step compiling an exo in the reports, it fails when the exo doesn't compile, even
+/// if it has no check
+const COMPILATION_STEP: &str = "Compilation";
+
+/// Name of the status of a check, as written in the reports
+fn status_name(run: &HeadlessRun, result: &ExoCheckResult) -> &'static str {
+    if run.compilation_error.is_some() {
+        return "not_run";
+    }
+    match result.state.status {
+        CheckStatus::Passed => "passed",
+        CheckStatus::Failed(..) => "failed",
+        CheckStatus::RunFail(_) => "run_fail",
+        _ => "not_run",
+    }
+}
+
+/// Returns the expected output and the diff without colors of a failed check
+fn failure_details(result: &ExoCheckResult) -> Option<(&str, String)> {
+    match &result.state.status {
+        CheckStatus::Failed(expected, _, diff) => Some((
+            expected,
+            console::strip_ansi_codes(&diff.to_ansi_colors()).to_string(),
+        )),
+        _ => None,
+    }
+}
+
+/// Describes why a check didn't pass, None if it passed
+fn failure_message(run: &HeadlessRun, result: &ExoCheckResult) -> Option<String> {
+    if let Some(err) = &run.compilation_error {
+        return Some(err.clone());
+    }
+    match &result.state.status {
+        CheckStatus::Passed => None,
+        CheckStatus::Failed(..) => Some(String::from("Output doesn't match the expected output")),
+        CheckStatus::RunFail(err) => Some(format!("Couldn't run the exo: {}", err)),
+        _ => Some(String::from("Check not run")),
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    course: &'a str,
+    passed: usize,
+    total: usize,
+    exos: Vec<JsonExo<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonExo<'a> {
+    name: &'a str,
+    folder: String,
+    passed: bool,
+    duration_ms: u128,
+    compilation_error: Option<&'a str>,
+    compilation_output: &'a [String],
+    checks: Vec<JsonCheck<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonCheck<'a> {
+    name: &'a str,
+    args: &'a [String],
+    status: &'static str,
+    duration_ms: Option<u128>,
+    output: String,
+    expected: Option<&'a str>,
+    diff: Option<String>,
+}
+
+fn json_report(course_name: &str, runs: &[HeadlessRun]) -> String {
+    let exos: Vec<JsonExo> = runs
+        .iter()
+        .map(|run| JsonExo {
+            name: &run.report.exo.name,
+            folder: Headless::relative_folder(&run.report.exo)
+                .display()
+                .to_string(),
+            passed: run.passed(),
+            duration_ms: run.duration.as_millis(),
+            compilation_error: run.compilation_error.as_deref(),
+            compilation_output: &run.report.compilation_output,
+            checks: run
+                .report
+                .check_results
+                .iter()
+                .map(|result| {
+                    let details = failure_details(result);
+                    JsonCheck {
+                        name: &result.state.check.name,
+                        args: &result.state.check.args,
+                        status: status_name(run, result),
+                        duration_ms: result.duration.map(|duration| duration.as_millis()),
+                        output: result.output.join("\n"),
+                        expected: details.as_ref().map(|(expected, _)| *expected),
+                        diff: details.map(|(_, diff)| diff),
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+    let report = JsonReport {
+        course: course_name,
+        passed: runs.iter().filter(|run| run.passed()).count(),
+        total: runs.len(),
+        exos,
+    };
+    // The report only contains strings and numbers, it can't fail to serialize
+    serde_json::to_string_pretty(&report).unwrap_or_default() + "\n"
+}
+
+/// Escapes the special characters of XML and removes the control characters it doesn't allow
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in console::strip_ansi_codes(text).chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn seconds(duration: Option<Duration>) -> String {
+    format!("{:.3}", duration.unwrap_or_default().as_secs_f64())
+}
+
+fn junit_report(course_name: &str, runs: &[HeadlessRun]) -> String {
+    let count = |filter: fn(&HeadlessRun, &ExoCheckResult) -> bool| {
+        runs.iter()
+            .flat_map(|run| run.report.check_results.iter().map(move |r| (run, r)))
+            .filter(|(run, result)| filter(run, result))
+            .count()
+    };
+    let compilation_failures = runs
+        .iter()
+        .filter(|run| run.compilation_error.is_some())
+        .count();
+    let tests = count(|_, _| true) + runs.len();
+    let failures = count(|run, result| status_name(run, result) == "failed") + compilation_failures;
+    let errors = count(|run, result| !matches!(status_name(run, result), "passed" | "failed"));
+    let total_time: Duration = runs.iter().map(|run| run.duration).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+        escape_xml(course_name),
+        tests,
+        failures,
+        errors,
+        seconds(Some(total_time))
+    ));
+    for run in runs {
+        let exo = &run.report.exo;
+        let folder = Headless::relative_folder(exo).display().to_string();
+        let results = &run.report.check_results;
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+            escape_xml(&exo.name),
+            results.len() + 1,
+            results
+                .iter()
+                .filter(|result| status_name(run, result) == "failed")
+                .count()
+                + run.compilation_error.iter().count(),
+            results
+                .iter()
+                .filter(|result| !matches!(status_name(run, result), "passed" | "failed"))
+                .count(),
+            seconds(Some(run.duration))
+        ));
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\">\n",
+            COMPILATION_STEP,
+            escape_xml(&folder),
+            seconds(None)
+        ));
+        if let Some(err) = &run.compilation_error {
+            xml.push_str(&format!(
+                "      <failure message=\"{}\"/>\n",
+                escape_xml(err)
+            ));
+        }
+        if !run.report.compilation_output.is_empty() {
+            xml.push_str(&format!(
+                "      <system-err>{}</system-err>\n",
+                escape_xml(&run.report.compilation_output.join("\n"))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+        for result in results {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+                escape_xml(&result.state.check.name),
+                escape_xml(&folder),
+                seconds(result.duration)
+            ));
+            let Some(message) = failure_message(run, result) else {
+                xml.push_str("/>\n");
+                continue;
+            };
+            xml.push_str(">\n");
+            let tag = if status_name(run, result) == "failed" {
+                "failure"
+            } else {
+                "error"
+            };
+            let details = failure_details(result)
+                .map(|(_, diff)| diff)
+                .unwrap_or_default();
+            xml.push_str(&format!(
+                "      <{tag} message=\"{}\">{}</{tag}>\n",
+                escape_xml(&message),
+                escape_xml(&details)
+            ));
+            if !result.output.is_empty() {
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    escape_xml(&result.output.join("\n"))
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Writes `text` as a YAML block scalar indented for a TAP diagnostic
+fn tap_block(key: &str, text: &str) -> String {
+    let mut block = format!("  {}: |\n", key);
+    for line in console::strip_ansi_codes(text).lines() {
+        block.push_str(&format!("    {}\n", line));
+    }
+    block
+}
+
+fn tap_report(runs: &[HeadlessRun]) -> String {
+    // Every exo has its compilation step followed by its checks
+    let total: usize = runs
+        .iter()
+        .map(|run| run.report.check_results.len() + 1)
+        .sum();
+    let mut tap = format!("TAP version 13\n1..{}\n", total);
+    let mut test_number = 0;
+    for run in runs {
+        tap.push_str(&format!("# {}\n", run.report.exo.name));
+        test_number += 1;
+        let description = format!("{} - {}", run.report.exo.name, COMPILATION_STEP);
+        match &run.compilation_error {
+            None => tap.push_str(&format!("ok {} - {}\n", test_number, description)),
+            Some(err) => {
+                tap.push_str(&format!("not ok {} - {}\n", test_number, description));
+                tap.push_str("  ---\n");
+                tap.push_str(&format!("  message: {:?}\n", err));
+                tap.push_str("  status: compilation_failed\n");
+                if !run.report.compilation_output.is_empty() {
+                    tap.push_str(&tap_block(
+                        "output",
+                        &run.report.compilation_output.join("\n"),
+                    ));
+                }
+                tap.push_str("  ...\n");
+            }
+        }
+        for result in &run.report.check_results {
+            test_number += 1;
+            let description = format!("{} - {}", run.report.exo.name, result.state.check.name);
+            let Some(message) = failure_message(run, result) else {
+                tap.push_str(&format!("ok {} - {}\n", test_number, description));
+                continue;
+            };
+            tap.push_str(&format!("not ok {} - {}\n", test_number, description));
+            tap.push_str("  ---\n");
+            tap.push_str(&format!("  message: {:?}\n", message));
+            tap.push_str(&format!("  status: {}\n", status_name(run, result)));
+            if let Some(duration) = result.duration {
+                tap.push_str(&format!("  duration_ms: {}\n", duration.as_millis()));
+            }
+            if let Some((expected, diff)) = failure_details(result) {
+                tap.push_str(&tap_block("expected", expected));
+                tap.push_str(&tap_block("got", &result.output.join("\n")));
+                tap.push_str(&tap_block("diff", &diff));
+            }
+            tap.push_str("  ...\n");
+        }
+    }
+    tap
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        app::exo_status_report::ExoStatusReport,
+        core::diff::diff::Diff,
+        models::{
+            check::{Check, CheckTest, Comparison, ExpectedSource},
+            exo::Exo,
+            exo_metadata::ExoMetadata,
+            exo_state::ExoState,
+        },
+    };
+
+    use super::*;
+
+    fn check(name: &str) -> Check {
+        Check {
+            name: String::from(name),
+            args: vec![],
+            stdin: None,
+            test: CheckTest::Output {
+                expected: String::from("Hello"),
+            },
+            source: ExpectedSource::Inherited,
+        }
+    }
+
+    fn run() -> HeadlessRun {
+        let exo = Exo {
+            name: String::from("Hello <world>"),
+            instruction: None,
+            language: None,
+            state: ExoState::Todo,
+            files: vec![],
+            solutions: vec![],
+            checks: vec![check("Passing"), check("Failing")],
+            favorite: false,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
+            requires: vec![],
+            locked: None,
+            folder: PathBuf::new(),
+        };
+        let mut report = ExoStatusReport::new(&exo, PathBuf::new());
+        report.check_results[0].state.status = CheckStatus::Passed;
+        report.check_results[0].output = vec![String::from("Hello")];
+        report.check_results[0].duration = Some(Duration::from_millis(12));
+        report.check_results[1].state.status = CheckStatus::Failed(
+            String::from("Hello"),
+            String::from("Bye"),
+            Diff::calculate_difference("Hello", "Bye", None),
+        );
+        report.check_results[1].output = vec![String::from("Bye")];
+        report.check_results[1].duration = Some(Duration::from_millis(3));
+        HeadlessRun {
+            report,
+            compilation_error: None,
+            duration: Duration::from_millis(500),
+        }
+    }
+
+    #[test]
+    fn json_report_has_every_check() {
+        let json: serde_json::Value =
+            serde_json::from_str(&ReportFormat::Json.render("Course", &[run()])).unwrap();
+        assert_eq!(json["passed"], 0);
+        assert_eq!(json["total"], 1);
+        let checks = &json["exos"][0]["checks"];
+        assert_eq!(checks[0]["status"], "passed");
+        assert_eq!(checks[0]["duration_ms"], 12);
+        assert_eq!(checks[1]["status"], "failed");
+        assert_eq!(checks[1]["output"], "Bye");
+        assert_eq!(checks[1]["expected"], "Hello");
+    }
+
+    #[test]
+    fn junit_report_escapes_names() {
+        let xml = ReportFormat::Junit.render("Course", &[run()]);
+        assert!(xml.contains("<testsuite name=\"Hello &lt;world&gt;\" tests=\"3\" failures=\"1\" errors=\"0\" time=\"0.500\">"));
+        assert!(xml.contains(
+            "<testcase name=\"Compilation\" classname=\"\" time=\"0.000\">\n    </testcase>"
+        ));
+        assert!(xml.contains("<testcase name=\"Passing\" classname=\"\" time=\"0.012\"/>"));
+        assert!(xml.contains("<failure message=\"Output doesn&apos;t match the expected output\">"));
+    }
+
+    #[test]
+    fn tap_report_numbers_checks() {
+        let tap = ReportFormat::Tap.render("Course", &[run()]);
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(lines[0], "TAP version 13");
+        assert_eq!(lines[1], "1..3");
+        assert_eq!(lines[3], "ok 1 - Hello <world> - Compilation");
+        assert_eq!(lines[4], "ok 2 - Hello <world> - Passing");
+        assert_eq!(lines[5], "not ok 3 - Hello <world> - Failing");
+        assert!(tap.contains("  status: failed\n  duration_ms: 3\n"));
+    }
+
+    #[test]
+    fn compilation_error_fails_every_check() {
+        let mut run = run();
+        run.compilation_error = Some(String::from("Compilation failed"));
+        let xml = ReportFormat::Junit.render("Course", &[run]);
+        assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"2\""));
+    }
+
+    #[test]
+    fn compilation_error_fails_exos_without_checks() {
+        let mut run = run();
+        run.report.check_results.clear();
+        run.compilation_error = Some(String::from("Compilation failed"));
+        run.report.compilation_output = vec![String::from("main.c:1: error")];
+        let xml = ReportFormat::Junit.render("Course", std::slice::from_ref(&run));
+        assert!(xml.contains("tests=\"1\" failures=\"1\" errors=\"0\""));
+        assert!(xml.contains("<failure message=\"Compilation failed\"/>\n      <system-err>main.c:1: error</system-err>"));
+
+        let tap = ReportFormat::Tap.render("Course", &[run]);
+        assert!(tap.starts_with(
+            "TAP version 13\n1..1\n# Hello <world>\nnot ok 1 - Hello <world> - Compilation\n"
+        ));
+        assert!(tap.contains("  status: compilation_failed\n  output: |\n    main.c:1: error\n"));
+    }
+}
@@ -39,5 +39,7 @@ impl App {
                 cr.check_results[run_id].state.status = CheckStatus::RunFail(err);
             }
         }
+        // No check is started for this run, show the failure now
+        self.on_new_check_update();
     }
 }
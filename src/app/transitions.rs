@@ -1,8 +1,8 @@
 use std::{path::PathBuf, sync::Arc};
 
 use crate::{
-    core::file_utils::file_utils::read_file,
-    models::{check_state::CheckState, ui_state::UiState},
+    core::{compiler::diagnostic::Diagnostic, file_utils::file_utils::read_file},
+    models::{check_state::CheckState, exo_state::ExoState, project::Project, ui_state::UiState},
 };
 
 use super::app::App;
@@ -10,7 +10,9 @@ use super::app::App;
 /// Helper functions to refactor setting the ui state
 impl App {
     pub(super) fn go_to_home(&mut self) {
-        self.set_ui_state(UiState::Home);
+        self.set_ui_state(UiState::Home {
+            errors: self.startup_errors.clone(),
+        });
     }
     pub(super) fn go_to_skill_selection(&mut self) {
         self.set_ui_state(UiState::SkillSelection {
@@ -49,7 +51,13 @@ impl App {
             ),
         })
     }
-    pub(super) fn go_to_compilation_error(&mut self, scroll_offset: usize, error: String) {
+    pub(super) fn go_to_compilation_error(
+        &mut self,
+        scroll_offset: usize,
+        error: String,
+        diagnostics: Vec<Diagnostic>,
+        diagnostic_idx: usize,
+    ) {
         self.set_ui_state(UiState::CompileError {
             exo: Arc::new(
                 self.project.skills[self.project.state.curr_skill_idx].exos
@@ -58,6 +66,8 @@ impl App {
             ),
             scroll_offset,
             error,
+            diagnostics,
+            diagnostic_idx,
         })
     }
     pub(super) fn go_to_check_results(&mut self, scroll_offset: usize, checks: Vec<CheckState>) {
@@ -103,6 +113,36 @@ impl App {
         };
         self.set_ui_state(state);
     }
+    pub(super) fn go_to_reset_confirmation(&mut self, last_state: Box<UiState>) {
+        self.set_ui_state(UiState::ConfirmReset {
+            exo: Arc::new(self.current_exo().clone()),
+            last_state,
+        })
+    }
+    /// Marks the current exo as done, which can unlock the next exos, and shows its solution
+    pub(super) fn finish_exo(&mut self) {
+        Project::set_exo_state(self.current_exo(), ExoState::Done);
+        self.current_exo_mut().state = ExoState::Done;
+        self.project.refresh_locks();
+        self.go_to_solution(0, 0);
+    }
+    /// Reveals the next hint of the current exo and shows it on the train page
+    pub(super) fn show_next_hint(&mut self) {
+        let hints_used = Project::use_hint(self.current_exo());
+        self.current_exo_mut().hints_used = hints_used;
+        self.refresh_train_exo();
+    }
+    /// Shows the exo of the project on the train page, after it was updated
+    pub(super) fn refresh_train_exo(&mut self) {
+        let mut state = self.ui_state.clone();
+        match &mut state {
+            UiState::Compiling { exo }
+            | UiState::CompileError { exo, .. }
+            | UiState::CheckResults { exo, .. } => *exo = Arc::new(self.current_exo().clone()),
+            _ => return,
+        }
+        self.set_ui_state(state);
+    }
     pub(super) fn go_to_help(&mut self, last_state: Box<UiState>, scroll_offset: usize) {
         self.set_ui_state(UiState::Help {
             last_state,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::models::{
     check_state::{CheckState, CheckStatus},
     exo::Exo,
@@ -12,6 +14,13 @@ impl App {
             [self.project.state.curr_exo_idx]
     }
 
+    /// Gets the current exo to update it in the project, the skills and exos shared with the
+    /// ui are cloned if needed
+    pub(super) fn current_exo_mut(&mut self) -> &mut Exo {
+        let skill = &mut Arc::make_mut(&mut self.project.skills)[self.project.state.curr_skill_idx];
+        &mut Arc::make_mut(&mut skill.exos)[self.project.state.curr_exo_idx]
+    }
+
     /// Checks if all checks in `checks` have passed
     pub(super) fn all_checks_passed(checks: &Vec<CheckState>) -> bool {
         checks
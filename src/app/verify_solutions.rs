@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+
+use console::style;
+
+use crate::models::{check_state::CheckStatus, exo::Exo};
+
+use super::headless::{Headless, HeadlessRun};
+
+/// Result of the verification of the solution of one exo
+enum SolutionStatus {
+    /// Every check passed with the solution files
+    Passed(usize),
+    /// The solution compiled but some checks failed
+    ChecksFailed(usize, usize),
+    /// The solution couldn't be compiled, contains the reason
+    CompilationFailed(String),
+    /// The exo has no solution to verify, it doesn't make the verification fail
+    NoSolution,
+    /// PLX can't build exos in this language yet, it doesn't make the verification fail
+    Unsupported,
+    /// The solution couldn't be copied in the temporary folder
+    CopyFailed(String),
+}
+
+impl SolutionStatus {
+    fn is_failure(&self) -> bool {
+        !matches!(
+            self,
+            SolutionStatus::Passed(_) | SolutionStatus::NoSolution | SolutionStatus::Unsupported
+        )
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            SolutionStatus::Passed(total) => format!("{}/{} checks passed", total, total),
+            SolutionStatus::ChecksFailed(passed, total) => {
+                format!("{}/{} checks passed", passed, total)
+            }
+            SolutionStatus::CompilationFailed(err) => err.to_lowercase(),
+            SolutionStatus::NoSolution => String::from("no solution"),
+            SolutionStatus::Unsupported => String::from("language not supported"),
+            SolutionStatus::CopyFailed(err) => format!("couldn't copy the solution ({})", err),
+        }
+    }
+}
+
+/// Functions related to verifying that the solutions of the exos pass their own checks
+impl Headless {
+    /// Checks the solution of every exo inside the target folder and prints a table of the results
+    /// Returns false if the solution of at least one exo doesn't pass all its checks
+    pub fn verify_solutions(&self) -> bool {
+        let exos = self.target_exos();
+        let results: Vec<(&Exo, SolutionStatus)> = exos
+            .iter()
+            .map(|exo| (*exo, self.verify_solution(exo)))
+            .collect();
+
+        let name_width = results
+            .iter()
+            .map(|(exo, _)| exo.name.chars().count())
+            .max()
+            .unwrap_or_default();
+        println!("{:<width$}  Solution", "Exo", width = name_width);
+        for (exo, status) in &results {
+            let status_text = match status {
+                SolutionStatus::NoSolution | SolutionStatus::Unsupported => {
+                    style(status.describe()).dim()
+                }
+                _ if status.is_failure() => style(status.describe()).red(),
+                _ => style(status.describe()).green(),
+            };
+            println!("{:<width$}  {}", exo.name, status_text, width = name_width);
+        }
+
+        let failed = results
+            .iter()
+            .filter(|(_, status)| status.is_failure())
+            .count();
+        let summary = format!("\n{}/{} solutions failed", failed, results.len());
+        if failed == 0 {
+            println!("{}", style(summary).green().bold());
+            true
+        } else {
+            println!("{}", style(summary).red().bold());
+            false
+        }
+    }
+
+    /// Checks that the solution of the exo passes all its checks
+    fn verify_solution(&self, exo: &Exo) -> SolutionStatus {
+        if exo.solutions.is_empty() {
+            return SolutionStatus::NoSolution;
+        }
+        if exo.compiler().is_none() {
+            return SolutionStatus::Unsupported;
+        }
+        match self.run_solution(exo) {
+            Ok(run) => SolutionStatus::from(&run),
+            Err(err) => SolutionStatus::CopyFailed(err.to_string()),
+        }
+    }
+
+    /// Builds the exo with its solution files in a temporary folder and runs its checks
+    /// Fails if the solution couldn't be copied
+    pub(super) fn run_solution(&self, exo: &Exo) -> std::io::Result<HeadlessRun> {
+        let folder = tempfile::tempdir()?;
+        let solved_exo = Headless::copy_with_solutions(exo, folder.path())?;
+        let build_folder = folder.path().join("build");
+        std::fs::create_dir(&build_folder)?;
+        Ok(self.run_exo_in(&solved_exo, &build_folder))
+    }
+
+    /// Copies the exo files in `folder` and replaces them with their solution, keeping their
+    /// path inside the exo, e.g. `src/main.sol.c` is copied to `folder/src/main.c`
+    /// The shared files are still compiled from the course
+    /// Returns a copy of the exo pointing to the copied files
+    fn copy_with_solutions(exo: &Exo, folder: &Path) -> std::io::Result<Exo> {
+        let relative = |file: &PathBuf| {
+            file.strip_prefix(&exo.folder)
+                .map(Path::to_path_buf)
+                .unwrap_or(PathBuf::from(file.file_name().unwrap_or_default()))
+        };
+        let copy = |from: &PathBuf, to: &PathBuf| {
+            if let Some(parent) = to.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(from, to)
+        };
+        let mut files = Vec::new();
+        for file in &exo.files {
+            let target = folder.join(relative(file));
+            copy(file, &target)?;
+            files.push(target);
+        }
+        for solution in &exo.solutions {
+            let Some(name) = solution.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let target = folder
+                .join(relative(solution))
+                .with_file_name(name.replacen(".sol.", ".", 1));
+            copy(solution, &target)?;
+            if !files.contains(&target) {
+                files.push(target);
+            }
+        }
+        let mut solved_exo = exo.clone();
+        solved_exo.folder = PathBuf::from(folder);
+        solved_exo.files = files;
+        Ok(solved_exo)
+    }
+}
+
+impl From<&HeadlessRun> for SolutionStatus {
+    fn from(run: &HeadlessRun) -> Self {
+        if let Some(err) = &run.compilation_error {
+            return SolutionStatus::CompilationFailed(err.clone());
+        }
+        let total = run.report.check_results.len();
+        let passed = run
+            .report
+            .check_results
+            .iter()
+            .filter(|result| result.state.status == CheckStatus::Passed)
+            .count();
+        if passed == total {
+            SolutionStatus::Passed(total)
+        } else {
+            SolutionStatus::ChecksFailed(passed, total)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{app::headless::tests::mock_course, core::parser::from_dir::FromDir};
+
+    use super::*;
+
+    #[test]
+    fn broken_solution_fails_the_verification() {
+        let build = tempfile::tempdir().unwrap();
+        let headless = mock_course("examples/mock-plx-project/intro", build.path());
+        let exo_folder = PathBuf::from("examples/mock-plx-project/intro/basic-output");
+        let (exo, _) = Exo::from_dir(&exo_folder).unwrap();
+        let status = headless.verify_solution(&exo);
+        assert!(matches!(status, SolutionStatus::Passed(1)));
+
+        let exo_folder = PathBuf::from("examples/mock-plx-project/mock-skill/broken-solution");
+        let (exo, _) = Exo::from_dir(&exo_folder).unwrap();
+        let status = headless.verify_solution(&exo);
+        assert!(matches!(status, SolutionStatus::ChecksFailed(0, 1)));
+        assert!(status.is_failure());
+    }
+
+    #[test]
+    fn solution_files_replace_exo_files() {
+        let exo_folder = PathBuf::from("examples/mock-plx-project/intro/basic-args");
+        let (exo, _) = Exo::from_dir(&exo_folder).unwrap();
+        let folder = tempfile::tempdir().unwrap();
+
+        let solved_exo = Headless::copy_with_solutions(&exo, folder.path()).unwrap();
+        assert_eq!(solved_exo.folder, folder.path());
+        assert_eq!(solved_exo.files, vec![folder.path().join("main.c")]);
+        assert_eq!(
+            std::fs::read_to_string(folder.path().join("main.c")).unwrap(),
+            std::fs::read_to_string(exo_folder.join("main.sol.c")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use log::LevelFilter;
+
+use crate::{
+    app::{gradebook::GradebookFormat, report::ReportFormat},
+    models::exo_metadata::{Difficulty, ExoFilter},
+};
+
+/// Practice programming exos in a delightful Learning eXperience
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    /// Root folder of the course, the current folder is used by default
+    #[arg(long, global = true, value_name = "DIR")]
+    pub course: Option<PathBuf>,
+    /// File in which the logs are written
+    #[arg(long, global = true, value_name = "FILE", default_value = "debug.log")]
+    pub log_file: PathBuf,
+    /// Minimum level of the logs: off, error, warn, info, debug or trace
+    #[arg(long, global = true, value_name = "LEVEL", default_value_t = LevelFilter::Trace)]
+    pub log_level: LevelFilter,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum Command {
+    /// Start the TUI, this is the default command
+    Tui,
+    /// Compile and check exos without the TUI, fails if an exo doesn't pass all its checks
+    Check {
+        /// Course, skill or exo folder to check, the whole course by default
+        path: Option<PathBuf>,
+        /// Also write the results in a machine-readable format
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        report: Option<ReportFormat>,
+        /// File in which the report is written, stdout by default
+        #[arg(long, value_name = "FILE", requires = "report")]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Check that the solution of each exo passes all its checks
+    VerifySolutions {
+        /// Course, skill or exo folder to verify, the whole course by default
+        path: Option<PathBuf>,
+    },
+    /// Write the output of the solution as the expected output of the checks that don't match it
+    Bless {
+        /// Exo folder, or skill or course folder to bless every exo inside
+        path: PathBuf,
+        /// Only show the changes, exo.toml files are not modified
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Grade the submissions of students and write a gradebook with the score of every exo
+    Grade {
+        /// Course, skill or exo folder to grade, the whole course by default
+        path: Option<PathBuf>,
+        /// Folder containing one folder per student, organized like the course
+        #[arg(long, value_name = "DIR")]
+        submissions: PathBuf,
+        /// Format of the gradebook
+        #[arg(long, value_enum, default_value_t = GradebookFormat::Csv)]
+        format: GradebookFormat,
+        /// File in which the gradebook is written, stdout by default
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Number of submissions graded at the same time, the number of CPUs by default
+        #[arg(long, short)]
+        jobs: Option<usize>,
+    },
+    /// Create a new course, skill or exo from a template
+    New {
+        #[command(subcommand)]
+        what: NewCommand,
+    },
+    /// List the skills and exos of the course with their progress
+    #[command(visible_alias = "status")]
+    List {
+        /// Print the progress as JSON
+        #[arg(long)]
+        json: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Parse the course and report the problems found in its files
+    Validate,
+    /// Reset the progress of exos
+    Reset {
+        /// Course, skill or exo folder to reset
+        path: PathBuf,
+    },
+}
+
+/// Selects exos by the metadata of their exo.toml
+#[derive(Args, Debug, Default, PartialEq)]
+pub struct FilterArgs {
+    /// Only use the exos with this tag, can be repeated to require several tags
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+    /// Only use the exos of this difficulty
+    #[arg(long, value_enum)]
+    pub difficulty: Option<Difficulty>,
+}
+
+impl From<&FilterArgs> for ExoFilter {
+    fn from(args: &FilterArgs) -> Self {
+        Self {
+            tags: args.tags.clone(),
+            difficulty: args.difficulty,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, PartialEq)]
+pub enum NewCommand {
+    /// Create a course folder with its course.toml in the current folder
+    Course {
+        /// Name of the course, the folder name is generated from it
+        name: String,
+    },
+    /// Create a skill folder in the course and add it to the course skills
+    Skill {
+        /// Name of the skill, the folder name is generated from it
+        name: String,
+    },
+    /// Create a C exo in a skill with a starter file, its solution and a sample check
+    Exo {
+        /// Name of the exo, the folder name is generated from it
+        name: String,
+        /// Skill folder of the exo, the skill containing the current folder by default
+        #[arg(long, value_name = "DIR")]
+        skill: Option<PathBuf>,
+    },
+}
+
+impl Cli {
+    /// Returns the folder of the course
+    pub fn course_folder(&self) -> PathBuf {
+        self.course.clone().unwrap_or(PathBuf::from("."))
+    }
+
+    /// Returns the folder targeted by a command, the course folder if `path` is None
+    /// When a course is given, paths that don't exist from the current folder are taken from the
+    /// course folder, so `plx --course ~/course check pointers` works from anywhere
+    pub fn target(&self, path: &Option<PathBuf>) -> PathBuf {
+        match (path, &self.course) {
+            (Some(path), Some(course)) if !path.exists() => course.join(path),
+            (Some(path), _) => path.clone(),
+            (None, _) => self.course_folder(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn cli_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn tui_is_the_default() {
+        let cli = Cli::try_parse_from(["plx"]).unwrap();
+        assert_eq!(cli.command, None);
+        assert_eq!(cli.log_file, PathBuf::from("debug.log"));
+        assert_eq!(cli.log_level, LevelFilter::Trace);
+        assert_eq!(cli.course_folder(), PathBuf::from("."));
+    }
+
+    #[test]
+    fn global_flags_after_subcommand() {
+        let cli = Cli::try_parse_from([
+            "plx",
+            "check",
+            "intro",
+            "--course",
+            "examples/full",
+            "--log-level",
+            "warn",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Check {
+                path: Some(PathBuf::from("intro")),
+                report: None,
+                output: None,
+                filter: FilterArgs::default()
+            })
+        );
+        assert_eq!(cli.log_level, LevelFilter::Warn);
+        assert_eq!(cli.course_folder(), PathBuf::from("examples/full"));
+    }
+
+    #[test]
+    fn check_targets_the_course_by_default() {
+        let cli = Cli::try_parse_from(["plx", "--course", "examples/full", "check"]).unwrap();
+        assert_eq!(cli.target(&None), PathBuf::from("examples/full"));
+    }
+
+    #[test]
+    fn target_is_found_in_the_course() {
+        let cli = Cli::try_parse_from(["plx", "--course", "examples/full", "check"]).unwrap();
+        assert_eq!(
+            cli.target(&Some(PathBuf::from("pointers"))),
+            PathBuf::from("examples/full/pointers")
+        );
+        assert_eq!(
+            cli.target(&Some(PathBuf::from("examples/full/intro"))),
+            PathBuf::from("examples/full/intro")
+        );
+    }
+
+    #[test]
+    fn report_output_needs_a_format() {
+        assert!(Cli::try_parse_from(["plx", "check", "--output", "report.xml"]).is_err());
+        let cli = Cli::try_parse_from([
+            "plx",
+            "check",
+            "--report",
+            "junit",
+            "--output",
+            "report.xml",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Check {
+                path: None,
+                report: Some(ReportFormat::Junit),
+                output: Some(PathBuf::from("report.xml")),
+                filter: FilterArgs::default()
+            })
+        );
+    }
+
+    #[test]
+    fn grade_needs_submissions() {
+        assert!(Cli::try_parse_from(["plx", "grade"]).is_err());
+        let cli =
+            Cli::try_parse_from(["plx", "grade", "--submissions", "students", "-j", "2"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Grade {
+                path: None,
+                submissions: PathBuf::from("students"),
+                format: GradebookFormat::Csv,
+                output: None,
+                jobs: Some(2)
+            })
+        );
+    }
+
+    #[test]
+    fn new_exo_in_a_skill() {
+        let cli =
+            Cli::try_parse_from(["plx", "new", "exo", "First pointer", "--skill", "pointers"])
+                .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::New {
+                what: NewCommand::Exo {
+                    name: String::from("First pointer"),
+                    skill: Some(PathBuf::from("pointers"))
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn status_is_an_alias_of_list() {
+        let cli = Cli::try_parse_from(["plx", "status", "--json"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::List {
+                json: true,
+                filter: FilterArgs::default()
+            })
+        );
+    }
+
+    #[test]
+    fn tags_can_be_repeated() {
+        let cli = Cli::try_parse_from([
+            "plx",
+            "check",
+            "--tag",
+            "pointers",
+            "--tag",
+            "strings",
+            "--difficulty",
+            "easy",
+        ])
+        .unwrap();
+        let Some(Command::Check { filter, .. }) = cli.command else {
+            panic!("check expected");
+        };
+        assert_eq!(
+            ExoFilter::from(&filter),
+            ExoFilter {
+                tags: vec![String::from("pointers"), String::from("strings")],
+                difficulty: Some(Difficulty::Easy)
+            }
+        );
+    }
+
+    #[test]
+    fn bless_dry_run() {
+        let cli = Cli::try_parse_from(["plx", "bless", "intro/basic-args", "--dry-run"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Bless {
+                path: PathBuf::from("intro/basic-args"),
+                dry_run: true
+            })
+        );
+    }
+
+    #[test]
+    fn reset_needs_a_path() {
+        assert!(Cli::try_parse_from(["plx", "reset"]).is_err());
+    }
+}
@@ -8,5 +8,6 @@ pub mod launcher;
 pub mod parser;
 pub mod process;
 pub mod runner;
+pub mod scaffold;
 pub mod watcher;
 pub mod work;
@@ -3,7 +3,7 @@ use std::sync::{atomic::AtomicBool, mpsc::Sender, Arc};
 use crate::{
     core::work::{work::Work, work_type::WorkType},
     models::{
-        check::{Check, CheckTest},
+        check::{Check, CheckTest, Comparison},
         event::Event,
     },
 };
@@ -16,6 +16,7 @@ pub struct Checker {
     id: usize,
     check: Arc<Check>,
     program_output: String,
+    comparison: Comparison,
 }
 
 impl Checker {
@@ -24,8 +25,14 @@ impl Checker {
             id,
             check,
             program_output,
+            comparison: Comparison::default(),
         }
     }
+    /// Sets how the program output is compared to the expected output
+    pub fn with_comparison(mut self, comparison: Comparison) -> Self {
+        self.comparison = comparison;
+        self
+    }
 }
 impl Work for Checker {
     /// Runs a check based on its type
@@ -33,7 +40,8 @@ impl Work for Checker {
         // Run dedicated checker based on check type
         match &self.check.test {
             CheckTest::Output { expected } => {
-                let output_checker = OutputChecker::new(self.id, &self.program_output, &expected);
+                let output_checker = OutputChecker::new(self.id, &self.program_output, &expected)
+                    .with_comparison(self.comparison);
                 output_checker.run(tx, stop);
             }
         }
@@ -5,7 +5,7 @@ use crate::{
         diff::diff::Diff,
         work::{work::Work, work_type::WorkType},
     },
-    models::event::Event,
+    models::{check::Comparison, event::Event},
 };
 
 #[derive(Debug)]
@@ -20,6 +20,7 @@ pub struct OutputChecker<'a> {
     id: usize,
     expected: &'a str,
     program_output: &'a str,
+    comparison: Comparison,
 }
 
 impl<'a> OutputChecker<'a> {
@@ -28,15 +29,28 @@ impl<'a> OutputChecker<'a> {
             id,
             expected,
             program_output,
+            comparison: Comparison::default(),
         }
     }
+    /// Sets how the program output is compared to the expected output
+    pub fn with_comparison(mut self, comparison: Comparison) -> Self {
+        self.comparison = comparison;
+        self
+    }
 }
 impl Work for OutputChecker<'_> {
     /// Use the Diff module to check for a difference between the output and expected
+    /// Both are normalized by the comparison first, the diff of a failed check shows the
+    /// original texts
     fn run(&self, tx: Sender<Event>, _stop: Arc<AtomicBool>) -> bool {
-        let diff = Diff::calculate_difference(&self.program_output, self.expected, None);
-
-        let event = if diff.contains_differences() {
+        let normalized = Diff::calculate_difference(
+            &self.comparison.normalize(self.program_output),
+            &self.comparison.normalize(self.expected),
+            None,
+        );
+
+        let event = if normalized.contains_differences() {
+            let diff = Diff::calculate_difference(self.program_output, self.expected, None);
             Event::OutputCheckFailed(self.id, diff)
         } else {
             Event::OutputCheckPassed(self.id)
@@ -145,4 +159,18 @@ mod test {
         let event = test("hello\n\nworld", output);
         assert!(matches!(event, Event::OutputCheckFailed(..)));
     }
+
+    #[test]
+    fn test_comparison_is_applied() {
+        let (tx, rx) = channel();
+        let checker = OutputChecker::new(0, "HELLO\n  World", "hello\nworld")
+            .with_comparison(Comparison::IgnoreCase);
+        checker.run(tx.clone(), Arc::new(AtomicBool::new(false)));
+        assert!(matches!(rx.recv().unwrap(), Event::OutputCheckFailed(..)));
+
+        let checker = OutputChecker::new(0, "hello\n  world", "hello\nworld")
+            .with_comparison(Comparison::IgnoreWhitespace);
+        checker.run(tx, Arc::new(AtomicBool::new(false)));
+        assert!(matches!(rx.recv().unwrap(), Event::OutputCheckPassed(..)));
+    }
 }
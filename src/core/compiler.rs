@@ -1,2 +1,5 @@
+pub mod build_cache;
 pub mod compile_runner;
 pub mod compiler;
+pub mod diagnostic;
+pub mod toolchain;
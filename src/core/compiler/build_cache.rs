@@ -0,0 +1,149 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::parser::object_creator::{create_object_from_file, write_object_to_file},
+    models::constants::BUILD_CACHE_FILE,
+};
+
+/// Build cache stored in the exo build folder
+/// It remembers the hash of the inputs used to produce each build artifact (object files, target
+/// binary) so artifacts whose inputs didn't change are not built again
+/// Hashes are only compared with hashes computed by the same PLX binary, if they can't be read or
+/// don't match, the artifact is simply rebuilt
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct BuildCache {
+    #[serde(default)]
+    artifacts: BTreeMap<String, String>,
+    /// Sources copied in the work folder of build systems, relative to it
+    #[serde(default)]
+    sources: Vec<std::path::PathBuf>,
+}
+
+impl BuildCache {
+    /// Loads the cache of `build_folder`, an empty cache is returned if there is none yet
+    pub fn load(build_folder: &std::path::Path) -> Self {
+        create_object_from_file::<BuildCache>(&build_folder.join(BUILD_CACHE_FILE))
+            .unwrap_or_default()
+    }
+
+    /// Stores the cache in `build_folder`
+    pub fn save(&self, build_folder: &std::path::Path) {
+        if let Err(err) = write_object_to_file(&build_folder.join(BUILD_CACHE_FILE), self) {
+            warn!("Couldn't save build cache {:?}", err);
+        }
+    }
+
+    /// Checks if `artifact` exists and was built from inputs with the same `hash`
+    pub fn is_up_to_date(&self, artifact: &std::path::Path, hash: &str) -> bool {
+        artifact.exists()
+            && BuildCache::key(artifact)
+                .and_then(|key| self.artifacts.get(&key))
+                .is_some_and(|stored| stored == hash)
+    }
+
+    /// Remembers that `artifact` was built from inputs with the given `hash`
+    pub fn set(&mut self, artifact: &std::path::Path, hash: String) {
+        if let Some(key) = BuildCache::key(artifact) {
+            self.artifacts.insert(key, hash);
+        }
+    }
+
+    /// Forgets about `artifact`, it will be built again next time
+    pub fn remove(&mut self, artifact: &std::path::Path) {
+        if let Some(key) = BuildCache::key(artifact) {
+            self.artifacts.remove(&key);
+        }
+    }
+
+    /// Remembers the `sources` copied in the work folder and returns the sources copied by the
+    /// previous build that are not part of them anymore
+    pub fn replace_sources(&mut self, sources: Vec<std::path::PathBuf>) -> Vec<std::path::PathBuf> {
+        let previous = std::mem::replace(&mut self.sources, sources);
+        previous
+            .into_iter()
+            .filter(|source| !self.sources.contains(source))
+            .collect()
+    }
+
+    fn key(artifact: &std::path::Path) -> Option<String> {
+        Some(String::from(artifact.file_name()?.to_str()?))
+    }
+
+    /// Hashes the content of every file in `files` and the given `args`
+    /// Returns None if one of the files can't be read
+    pub fn hash(files: &[std::path::PathBuf], args: &[String]) -> Option<String> {
+        let mut hasher = DefaultHasher::new();
+        args.hash(&mut hasher);
+        for file in files {
+            file.file_name().hash(&mut hasher);
+            std::fs::read(file).ok()?.hash(&mut hasher);
+        }
+        Some(format!("{:016x}", hasher.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn hash_depends_on_content_and_args() {
+        let files = vec![PathBuf::from("examples/basics/c/basic-output.c")];
+        let other_files = vec![PathBuf::from("examples/basics/c/nothing.c")];
+        let args = vec![String::from("-Wall")];
+        let hash = BuildCache::hash(&files, &args).unwrap();
+
+        assert_eq!(Some(hash.clone()), BuildCache::hash(&files, &args));
+        assert_ne!(Some(hash.clone()), BuildCache::hash(&files, &[]));
+        assert_ne!(Some(hash), BuildCache::hash(&other_files, &args));
+    }
+
+    #[test]
+    fn missing_file_has_no_hash() {
+        let files = vec![PathBuf::from("examples/basics/c/doesnt-exist.c")];
+        assert!(BuildCache::hash(&files, &[]).is_none());
+    }
+
+    #[test]
+    fn artifact_up_to_date_only_if_it_exists_with_same_hash() {
+        let existing = PathBuf::from("examples/basics/c/nothing.c");
+        let missing = PathBuf::from("target/doesnt-exist.o");
+        let mut cache = BuildCache::default();
+        cache.set(&existing, String::from("abc"));
+        cache.set(&missing, String::from("abc"));
+
+        assert!(cache.is_up_to_date(&existing, "abc"));
+        assert!(!cache.is_up_to_date(&existing, "def"));
+        assert!(!cache.is_up_to_date(&missing, "abc"));
+        cache.remove(&existing);
+        assert!(!cache.is_up_to_date(&existing, "abc"));
+    }
+
+    #[test]
+    fn removed_sources_are_returned() {
+        let mut cache = BuildCache::default();
+        let sources = vec![PathBuf::from("src/a.c"), PathBuf::from("test/a.c")];
+        assert!(cache.replace_sources(sources).is_empty());
+        assert_eq!(
+            cache.replace_sources(vec![PathBuf::from("src/a.c")]),
+            vec![PathBuf::from("test/a.c")]
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let folder = tempfile::tempdir().unwrap();
+        let mut cache = BuildCache::default();
+        cache.set(&PathBuf::from("main.c.o"), String::from("0123456789abcdef"));
+        cache.save(folder.path());
+        assert_eq!(cache, BuildCache::load(folder.path()));
+    }
+}
@@ -1,71 +1,343 @@
-use std::sync::{
-    atomic::AtomicBool,
-    mpsc::{self, Sender},
-    Arc,
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::AtomicBool,
+        mpsc::{self, Sender},
+        Arc,
+    },
 };
 
+use log::info;
+
 use crate::{
     core::{
+        file_utils::file_utils::{get_full_path, is_executable, list_dir_files_recursive},
         runner::runner::{RunEvent, Runner},
         work::{work::Work, work_type::WorkType},
     },
-    models::{event::Event, exo::Exo},
+    models::{constants::BUILD_SYSTEM_FOLDER_NAME, event::Event, exo::Exo},
 };
 
-use super::compiler::Compiler;
+use super::{build_cache::BuildCache, compiler::Compiler, toolchain::Toolchain};
+
+// Folders generated by build systems that never contain the target binary
+const IGNORED_BUILD_FOLDERS: [&str; 2] = ["CMakeFiles", ".git"];
+// Folder of the work folder in which the shared files outside of the exo folder are copied
+const SHARED_WORK_FOLDER: &str = "shared";
+
+// One command of the compilation
+struct BuildStep {
+    runner: Runner,
+    // Files read by the command, used to know if `artifact` needs to be built again
+    inputs: Vec<PathBuf>,
+    // File produced by the command, steps without artifact always run
+    artifact: Option<PathBuf>,
+}
+impl BuildStep {
+    fn new(runner: Runner, inputs: Vec<PathBuf>, artifact: Option<PathBuf>) -> Self {
+        Self {
+            runner,
+            inputs,
+            artifact,
+        }
+    }
+    // Hash of everything that can change the artifact, None if the step has no artifact
+    fn hash(&self) -> Option<String> {
+        self.artifact.as_ref()?;
+        BuildCache::hash(&self.inputs, &[self.runner.get_full_command()])
+    }
+}
 
 // Compile Runner
 // Represents the compilation worker
 pub struct CompileRunner {
-    runner: Runner,
+    // Every command to run in order, the compilation stops at the first failing one
+    steps: Vec<BuildStep>,
+    // Files that need to be copied to `work_folder` before running the commands, with their path
+    // inside the work folder, see `work_folder_path`
+    sources: Vec<(PathBuf, PathBuf)>,
+    // Folder in which build systems generate their artifacts, None for direct compiler calls
+    work_folder: Option<PathBuf>,
+    output_path: PathBuf,
 }
 impl CompileRunner {
     // Constructs a new compile runner
     // No update to the output path is done, if the underlying platform is windows, `.exe` must be
     // added to the output_path before calling this function
-    pub fn new(compiler: &Compiler, exo: &Exo, output_path: &std::path::PathBuf) -> Option<Self> {
-        let cmd = compiler.cmd();
-        let mut args = compiler.args(&exo.files);
-        match output_path.to_str() {
-            Some(path) => {
-                // TODO this should probably somewhere else like `compiler` because this is
-                // specific to gcc/g++
-                args.push(String::from("-fdiagnostics-color=always"));
-                args.push(String::from("-o"));
-                args.push(String::from(path));
+    // Build systems (make, cmake) generate their artifacts in a sub folder of the output path
+    // folder, the target binary they produce is then copied to `output_path`
+    // Compilers build each translation unit to an object file next to `output_path` and then
+    // link them, so only the units that changed since the last build are compiled again
+    pub fn new(
+        compiler: &Compiler,
+        toolchain: &Toolchain,
+        exo: &Exo,
+        output_path: &std::path::PathBuf,
+    ) -> Option<Self> {
+        let cmd = toolchain.cmd(compiler);
+        let build_folder = output_path.parent()?;
+        if compiler.is_build_system() {
+            let work_folder = build_folder.join(BUILD_SYSTEM_FOLDER_NAME);
+            let steps = compiler
+                .build_steps_args(&exo.folder, &work_folder)?
+                .into_iter()
+                .map(|args| BuildStep::new(Runner::new(String::from(cmd), args), vec![], None))
+                .collect();
+            // Make builds in source, so we build a copy of the exo files and the shared files
+            let sources = match compiler {
+                Compiler::Make => exo
+                    .files
+                    .iter()
+                    .chain(&exo.shared)
+                    .map(|file| Some((file.clone(), work_folder_path(file, &exo.folder)?)))
+                    .collect::<Option<Vec<_>>>()?,
+                _ => vec![],
+            };
+            return Some(Self {
+                steps,
+                sources,
+                work_folder: Some(work_folder),
+                output_path: output_path.clone(),
+            });
+        }
+        // Shared files are compiled like the exo files, without being copied in the exo folder
+        let files: Vec<PathBuf> = exo.files.iter().chain(&exo.shared).cloned().collect();
+        let units = compiler.args(&files);
+        // Every other exo file (headers...) can be included by any unit
+        let dependencies: Vec<PathBuf> = files
+            .iter()
+            .filter(|file| {
+                get_full_path(file)
+                    .ok()
+                    .and_then(|file| file.to_str().map(String::from))
+                    .is_none_or(|file| !units.contains(&file))
+            })
+            .cloned()
+            .collect();
+        // Shared headers are found with `#include "list.h"` from the exo files
+        let mut include_args: Vec<String> = Vec::new();
+        for folder in exo.shared.iter().filter_map(|file| file.parent()) {
+            let arg = format!("-I{}", folder.to_str()?);
+            if !include_args.contains(&arg) {
+                include_args.push(arg);
             }
-            None => return None,
         }
+        let mut steps = Vec::new();
+        let mut objects = Vec::new();
+        let mut object_names: Vec<String> = Vec::new();
+        for unit in units {
+            let unit = PathBuf::from(unit);
+            // Units with the same name (a shared `util.c` next to the exo `util.c`) get a suffix
+            let name = String::from(unit.file_name()?.to_str()?);
+            let same_name = object_names.iter().filter(|other| **other == name).count();
+            let object = if same_name == 0 {
+                build_folder.join(format!("{}.o", name))
+            } else {
+                build_folder.join(format!("{}.{}.o", name, same_name))
+            };
+            object_names.push(name);
+            // TODO this should probably somewhere else like `compiler` because this is
+            // specific to gcc/g++
+            let mut args = vec![
+                String::from("-c"),
+                String::from(unit.to_str()?),
+                String::from("-fdiagnostics-color=always"),
+            ];
+            args.extend(include_args.iter().cloned());
+            args.extend(
+                exo.flags
+                    .iter()
+                    .filter(|flag| !Compiler::is_link_flag(flag))
+                    .cloned(),
+            );
+            args.push(String::from("-o"));
+            args.push(String::from(object.to_str()?));
+            let mut inputs = vec![unit];
+            inputs.extend(dependencies.iter().cloned());
+            steps.push(BuildStep::new(
+                Runner::new(String::from(cmd), args),
+                inputs,
+                Some(object.clone()),
+            ));
+            objects.push(object);
+        }
+        let mut args = objects
+            .iter()
+            .map(|object| object.to_str().map(String::from))
+            .collect::<Option<Vec<String>>>()?;
+        // Libraries must come after the objects using them
+        args.extend(exo.flags.iter().cloned());
+        args.push(String::from("-o"));
+        args.push(String::from(output_path.to_str()?));
+        steps.push(BuildStep::new(
+            Runner::new(String::from(cmd), args),
+            objects,
+            Some(output_path.clone()),
+        ));
         Some(Self {
-            runner: Runner::new(String::from(cmd), args),
+            steps,
+            sources: vec![],
+            work_folder: None,
+            output_path: output_path.clone(),
         })
     }
     pub fn get_full_command(&self) -> String {
-        self.runner.get_full_command()
+        self.steps
+            .iter()
+            .map(|step| step.runner.get_full_command())
+            .collect::<Vec<String>>()
+            .join(" && ")
     }
-}
-impl Work for CompileRunner {
-    // Runs the underlying runner collecting its events and translating them to app Events
-    // See `models::Event` for more info
-    fn run(&self, tx: Sender<Event>, stop: Arc<AtomicBool>) -> bool {
+
+    // Creates the work folder and copies the sources to it
+    // Files are only copied if their content changed so build systems can detect what to rebuild
+    // Sources copied by the previous build that were removed from the exo are deleted
+    fn prepare_work_folder(&self, cache: &mut BuildCache) -> Result<(), std::io::Error> {
+        let Some(work_folder) = &self.work_folder else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(work_folder)?;
+        let destinations = self
+            .sources
+            .iter()
+            .map(|(_, destination)| destination.clone())
+            .collect();
+        for removed in cache.replace_sources(destinations) {
+            match std::fs::remove_file(work_folder.join(removed)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        for (source, destination) in &self.sources {
+            let destination = work_folder.join(destination);
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let content = std::fs::read(source)?;
+            if std::fs::read(&destination).ok().as_ref() != Some(&content) {
+                std::fs::write(&destination, content)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Finds the target binary produced by a build system and copies it to the output path
+    // When multiple executables were built, the most recent one is taken
+    fn collect_target(&self) -> Result<(), String> {
+        let Some(work_folder) = &self.work_folder else {
+            return Ok(());
+        };
+        let target = list_dir_files_recursive(work_folder, &IGNORED_BUILD_FOLDERS)
+            .map_err(|err| err.to_string())?
+            .into_iter()
+            .filter(|file| is_executable(file))
+            .max_by_key(|file| {
+                file.metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .ok_or(format!("No target binary found in {:?}", work_folder))?;
+        info!("Target found: {:?}", target);
+        std::fs::copy(&target, &self.output_path).map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    // Runs one compilation command and forwards its output
+    // Returns the success of the command or None if the process couldn't be created or the app
+    // isn't listening anymore
+    fn run_step(runner: &Runner, tx: &Sender<Event>, stop: Arc<AtomicBool>) -> Option<bool> {
         let (runner_tx, runner_rx) = mpsc::channel();
-        let _ = self.runner.run(runner_tx, stop);
+        let _ = runner.run(runner_tx, stop);
         while let Ok(msg) = runner_rx.recv() {
             let send = match msg {
-                RunEvent::ProcessCreationFailed(_) => {
-                    let _ = tx.send(Event::CompilationEnd(false));
-                    return false;
+                RunEvent::ProcessCreationFailed(err) => {
+                    let _ = tx.send(Event::CompilationOutputLine(format!(
+                        "Couldn't start `{}` ({})",
+                        runner.get_command(),
+                        err
+                    )));
+                    return None;
                 }
-                RunEvent::ProcessCreated => tx.send(Event::CompilationStart),
-
-                RunEvent::ProcessEnd(success) => tx.send(Event::CompilationEnd(success)),
-
+                RunEvent::ProcessCreated => Ok(()),
+                RunEvent::ProcessEnd(success) => return Some(success),
+                RunEvent::ProcessTimedOut(_) => return Some(false),
                 RunEvent::ProcessNewOutputLine(line) => tx.send(Event::CompilationOutputLine(line)),
             };
             if send.is_err() {
-                break;
+                return None;
+            }
+        }
+        None
+    }
+}
+// Path where `file` is copied inside the work folder, so nothing is written outside of it
+// Files of the exo folder keep their path relative to it, the shared files outside of it are
+// copied in `shared/` with their path relative to the closest folder containing both, like
+// `shared/common/list.c` for `../common/list.c`
+fn work_folder_path(file: &PathBuf, exo_folder: &PathBuf) -> Option<PathBuf> {
+    let file = get_full_path(file).ok()?;
+    let exo_folder = get_full_path(exo_folder).ok()?;
+    if let Ok(relative) = file.strip_prefix(&exo_folder) {
+        return Some(relative.to_path_buf());
+    }
+    let common = exo_folder
+        .ancestors()
+        .find(|ancestor| file.starts_with(ancestor))?;
+    Some(PathBuf::from(SHARED_WORK_FOLDER).join(file.strip_prefix(common).ok()?))
+}
+
+impl Work for CompileRunner {
+    // Runs the build steps collecting their events and translating them to app Events
+    // Steps whose artifact is up to date according to the build cache are skipped
+    // See `models::Event` for more info
+    fn run(&self, tx: Sender<Event>, stop: Arc<AtomicBool>) -> bool {
+        if tx.send(Event::CompilationStart).is_err() {
+            return false;
+        }
+        let cache_folder = self.output_path.parent().unwrap_or(&self.output_path);
+        let mut cache = BuildCache::load(cache_folder);
+        if let Err(err) = self.prepare_work_folder(&mut cache) {
+            let _ = tx.send(Event::CompilationOutputLine(format!(
+                "Couldn't prepare build folder ({})",
+                err
+            )));
+            let _ = tx.send(Event::CompilationEnd(false));
+            return false;
+        }
+        for step in &self.steps {
+            let hash = step.hash();
+            if let (Some(artifact), Some(hash)) = (&step.artifact, &hash) {
+                if cache.is_up_to_date(artifact, hash) {
+                    info!("{:?} is up to date", artifact);
+                    continue;
+                }
+            }
+            match CompileRunner::run_step(&step.runner, &tx, stop.clone()) {
+                Some(true) => {
+                    if let (Some(artifact), Some(hash)) = (&step.artifact, hash) {
+                        cache.set(artifact, hash);
+                    }
+                }
+                result => {
+                    if let Some(artifact) = &step.artifact {
+                        cache.remove(artifact);
+                    }
+                    cache.save(cache_folder);
+                    let _ = tx.send(Event::CompilationEnd(false));
+                    // The step could run but failed, this is a valid compilation result
+                    return result.is_some();
+                }
             }
         }
+        cache.save(cache_folder);
+        let success = match self.collect_target() {
+            Ok(()) => true,
+            Err(err) => {
+                let _ = tx.send(Event::CompilationOutputLine(err));
+                false
+            }
+        };
+        let _ = tx.send(Event::CompilationEnd(success));
         return true;
     }
 
@@ -96,8 +368,11 @@ mod test {
         output_path: &PathBuf,
     ) -> CompileRunner {
         assert!(!output_path.exists());
+        // Each test uses its own build folder so build caches don't interfere
+        std::fs::create_dir_all(output_path.parent().unwrap()).expect("Couldn't create folder");
         let exo = build_exo(&exo_path);
-        CompileRunner::new(compiler, &exo, &output_path).expect("Couldn't create compile runner")
+        CompileRunner::new(compiler, &Toolchain::default(), &exo, &output_path)
+            .expect("Couldn't create compile runner")
     }
     fn compile_and_assert_ok(compiler: CompileRunner, output_path: &PathBuf) {
         let (tx, rx) = mpsc::channel();
@@ -124,9 +399,13 @@ mod test {
             .join("intro")
             .join("basic-args");
         let output_path = if cfg!(windows) {
-            PathBuf::from("target").join("exo_one_file.exe")
+            PathBuf::from("target")
+                .join("exo_one_file")
+                .join("exo_one_file.exe")
         } else {
-            PathBuf::from("target").join("exo_one_file")
+            PathBuf::from("target")
+                .join("exo_one_file")
+                .join("exo_one_file")
         };
         let compiler = create_compiler(&Compiler::Gcc, &path, &output_path);
 
@@ -147,9 +426,9 @@ mod test {
             .join("queue");
 
         let output_path = if cfg!(windows) {
-            PathBuf::from("target").join("queue.exe")
+            PathBuf::from("target").join("queue").join("queue.exe")
         } else {
-            PathBuf::from("target").join("queue")
+            PathBuf::from("target").join("queue").join("queue")
         };
         let compiler = create_compiler(&Compiler::Gcc, &path, &output_path);
 
@@ -164,6 +443,165 @@ mod test {
         compile_and_assert_ok(compiler, &output_path);
     }
 
+    #[test]
+    fn compile_valid_exo_with_makefile() {
+        if cfg!(windows) {
+            return;
+        };
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("datastructures")
+            .join("queue-make");
+        let build_folder = PathBuf::from("target").join("queue-make");
+        std::fs::create_dir_all(&build_folder).expect("Couldn't create build folder");
+        let output_path = build_folder.join("exo");
+        let exo = build_exo(&path);
+        let compiler = exo.compiler().expect("No compiler found");
+        assert!(matches!(compiler, Compiler::Make));
+        let compiler = create_compiler(&compiler, &path, &output_path);
+
+        let command = compiler.get_full_command();
+
+        println!("Command: {}", command);
+        assert!(command.starts_with("make"));
+        assert!(command.contains(&format!(
+            "-C {}",
+            build_folder.join("project").to_str().unwrap()
+        )));
+        compile_and_assert_ok(compiler, &output_path);
+        assert!(build_folder.join("project").join("queue.o").exists());
+    }
+
+    #[test]
+    fn work_folder_keeps_the_source_paths() {
+        if cfg!(windows) {
+            return;
+        };
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("mock-skills")
+            .join("shared-files")
+            .join("make");
+        let build_folder = PathBuf::from("target").join("shared-make");
+        let _ = std::fs::remove_dir_all(&build_folder);
+        std::fs::create_dir_all(&build_folder).expect("Couldn't create build folder");
+        let output_path = build_folder.join("exo");
+        let mut exo = build_exo(&path);
+        let compiler =
+            CompileRunner::new(&Compiler::Make, &Toolchain::default(), &exo, &output_path)
+                .expect("Couldn't create compile runner");
+        compile_and_assert_ok(compiler, &output_path);
+        // The shared util.c is copied inside the work folder, apart from the exo util.c
+        let shared_copy = build_folder
+            .join("project")
+            .join("shared")
+            .join("common")
+            .join("util.c");
+        assert!(build_folder.join("project").join("util.c").exists());
+        assert!(shared_copy.exists());
+
+        exo.shared.clear();
+        let compiler =
+            CompileRunner::new(&Compiler::Make, &Toolchain::default(), &exo, &output_path)
+                .expect("Couldn't create compile runner");
+        let mut cache = BuildCache::load(&build_folder);
+        compiler.prepare_work_folder(&mut cache).unwrap();
+        assert!(!shared_copy.exists());
+        assert!(build_folder.join("project").join("util.c").exists());
+    }
+
+    #[test]
+    fn shared_files_are_copied_inside_the_work_folder() {
+        let course = PathBuf::from("examples").join("mock-plx-project");
+        let exo_folder = course.join("mock-skills").join("shared-files").join("make");
+        assert_eq!(
+            work_folder_path(&exo_folder.join("util.c"), &exo_folder),
+            Some(PathBuf::from("util.c"))
+        );
+        // Files from far up the course tree never go up from the work folder
+        assert_eq!(
+            work_folder_path(&course.join("intro").join("skill.toml"), &exo_folder),
+            Some(PathBuf::from("shared").join("intro").join("skill.toml"))
+        );
+    }
+
+    #[test]
+    fn cmake_exo_configures_then_builds() {
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("datastructures")
+            .join("queue-cmake");
+        let output_path = PathBuf::from("target").join("queue-cmake").join("exo");
+        let exo = build_exo(&path);
+        let compiler = exo.compiler().expect("No compiler found");
+        assert!(matches!(compiler, Compiler::CMake));
+        let compiler = create_compiler(&compiler, &path, &output_path);
+
+        let command = compiler.get_full_command();
+
+        println!("Command: {}", command);
+        let work_folder = PathBuf::from("target").join("queue-cmake").join("project");
+        let work_folder = work_folder.to_str().unwrap();
+        assert!(command.starts_with("cmake -S"));
+        assert!(command.contains(&format!(
+            "-B {} && cmake --build {}",
+            work_folder, work_folder
+        )));
+    }
+
+    #[test]
+    fn compile_only_modified_units() {
+        let exo_folder = tempfile::tempdir().unwrap();
+        let queue = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("datastructures")
+            .join("queue");
+        for file in ["exo.toml", "main.c", "queue.c", "queue.h"] {
+            std::fs::copy(queue.join(file), exo_folder.path().join(file)).unwrap();
+        }
+        let build_folder = tempfile::tempdir().unwrap();
+        let output_path = build_folder.path().join("queue");
+        let modified = |file: &str| {
+            build_folder
+                .path()
+                .join(file)
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .unwrap()
+        };
+        let exo_path = exo_folder.path().to_path_buf();
+
+        compile_and_assert_ok(
+            create_compiler(&Compiler::Gcc, &exo_path, &output_path),
+            &output_path,
+        );
+        let (main_object, queue_object) = (modified("main.c.o"), modified("queue.c.o"));
+
+        std::fs::write(
+            exo_folder.path().join("queue.c"),
+            "void queue_push(int value)\n{\n\t(void)value;\n}\n",
+        )
+        .unwrap();
+        compile_and_assert_ok(
+            create_compiler(&Compiler::Gcc, &exo_path, &output_path),
+            &output_path,
+        );
+        assert_eq!(main_object, modified("main.c.o"));
+        assert_ne!(queue_object, modified("queue.c.o"));
+
+        // A header change can impact every unit
+        std::fs::write(
+            exo_folder.path().join("queue.h"),
+            "void queue_push(int value);\n",
+        )
+        .unwrap();
+        compile_and_assert_ok(
+            create_compiler(&Compiler::Gcc, &exo_path, &output_path),
+            &output_path,
+        );
+        assert_ne!(main_object, modified("main.c.o"));
+    }
+
     #[test]
     fn compile_invalid_exo() {
         let path = PathBuf::from("examples")
@@ -171,9 +609,13 @@ mod test {
             .join("mock-skill")
             .join("doesntcompile");
         let output_path = if cfg!(windows) {
-            PathBuf::from("target").join("doesntcompile.exe")
+            PathBuf::from("target")
+                .join("doesntcompile")
+                .join("doesntcompile.exe")
         } else {
-            PathBuf::from("target").join("doesntcompile")
+            PathBuf::from("target")
+                .join("doesntcompile")
+                .join("doesntcompile")
         };
         let compiler = create_compiler(&Compiler::Gcc, &path, &output_path);
 
@@ -196,4 +638,63 @@ mod test {
         }
         assert!(!compilation_status.expect("Didn't receive the CompilationEnd event"));
     }
+
+    #[test]
+    fn link_flags_are_only_given_to_the_link_step() {
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("intro")
+            .join("basic-args");
+        let mut exo = build_exo(&path);
+        exo.flags = vec![String::from("-Wall"), String::from("-lm")];
+        let output_path = PathBuf::from("target").join("exo_flags").join("exo_flags");
+        let compiler =
+            CompileRunner::new(&Compiler::Gcc, &Toolchain::default(), &exo, &output_path)
+                .expect("Couldn't create compile runner");
+
+        let command = compiler.get_full_command();
+        let (compile, link) = command.split_once(" && ").unwrap();
+        assert!(compile.contains("-Wall") && !compile.contains("-lm"));
+        assert!(link.contains("-Wall -lm -o"));
+    }
+
+    #[test]
+    fn shared_files_are_compiled_and_included() {
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("mock-skills")
+            .join("shared-files");
+        let exo = build_exo(&path.join("exo"));
+        assert_eq!(exo.files, vec![path.join("exo").join("main.c")]);
+
+        let output_path = PathBuf::from("target")
+            .join("exo_shared")
+            .join("exo_shared");
+        let _ = std::fs::remove_dir_all(output_path.parent().unwrap());
+        std::fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+        let compiler =
+            CompileRunner::new(&Compiler::Gcc, &Toolchain::default(), &exo, &output_path)
+                .expect("Couldn't create compile runner");
+        assert!(compiler
+            .get_full_command()
+            .contains(&format!("-I{}", path.join("common").display())));
+        compile_and_assert_ok(compiler, &output_path);
+    }
+
+    #[test]
+    fn units_with_the_same_name_get_their_own_object() {
+        let path = PathBuf::from("examples")
+            .join("mock-plx-project")
+            .join("mock-skills")
+            .join("shared-files")
+            .join("same-name");
+        let output_path = PathBuf::from("target")
+            .join("exo_same_name")
+            .join("exo_same_name");
+        let _ = std::fs::remove_dir_all(output_path.parent().unwrap());
+        let compiler = create_compiler(&Compiler::Gcc, &path, &output_path);
+        let command = compiler.get_full_command();
+        assert!(command.contains("util.c.o") && command.contains("util.c.1.o"));
+        compile_and_assert_ok(compiler, &output_path);
+    }
 }
@@ -1,18 +1,26 @@
-use crate::core::file_utils::file_utils::get_full_path;
+use crate::{core::file_utils::file_utils::get_full_path, models::language::Language};
 
-#[derive(Debug)]
+/// File names that mark an exo as a `make` project
+pub const MAKEFILE_NAMES: [&str; 3] = ["Makefile", "makefile", "GNUmakefile"];
+/// File name that marks an exo as a `cmake` project
+pub const CMAKE_FILE_NAME: &str = "CMakeLists.txt";
+
+/// Represents the kind of compiler needed by an exo
+/// The actual command launched for each kind is chosen by the user, see `Toolchain`
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Compiler {
     Gcc,
     Gxx,
+    Make,
+    CMake,
 }
 
 impl Compiler {
-    /// Returns the correct command based on the compiler
-    pub fn cmd(&self) -> &'static str {
-        match self {
-            Compiler::Gcc => "gcc",
-            Compiler::Gxx => "g++",
-        }
+    /// Returns true if the exo is built by a build system (make, cmake) instead of a direct
+    /// compiler call. Build systems choose their own target name, so the target binary has to be
+    /// discovered once the build is done.
+    pub fn is_build_system(&self) -> bool {
+        matches!(self, Compiler::Make | Compiler::CMake)
     }
 
     /// Gets the correct arguments to launch the compiler
@@ -20,11 +28,57 @@ impl Compiler {
     /// Would make it easier to add new compilers without changing  `compile_runner`
     pub fn args(&self, files: &Vec<std::path::PathBuf>) -> Vec<String> {
         match self {
-            Compiler::Gcc => Compiler::collect_files_with_extension(files, &["c"]),
-            Compiler::Gxx => Compiler::collect_files_with_extension(files, &["c", "cpp", "cc"]),
+            Compiler::Gcc | Compiler::Gxx => {
+                let extensions = Language::built_with(self)
+                    .map(|language| language.source_extensions())
+                    .unwrap_or_default();
+                Compiler::collect_files_with_extension(files, &extensions)
+            }
+            // Build systems know their sources, see `build_steps_args`
+            Compiler::Make | Compiler::CMake => vec![],
         }
     }
 
+    /// Gets the arguments of every command needed to build a build system project
+    /// `source_folder` contains the project description (Makefile, CMakeLists.txt)
+    /// `build_folder` is the folder in which the artifacts should be generated
+    /// Each entry of the returned vector is one command to run with the toolchain command, in order
+    pub fn build_steps_args(
+        &self,
+        source_folder: &std::path::Path,
+        build_folder: &std::path::Path,
+    ) -> Option<Vec<Vec<String>>> {
+        let build_folder = String::from(build_folder.to_str()?);
+        match self {
+            // Make runs inside the build folder, exo files are copied there before the build
+            Compiler::Make => Some(vec![vec![
+                String::from("--no-print-directory"),
+                String::from("-C"),
+                build_folder,
+            ]]),
+            // CMake supports out of source builds, configure and then build
+            Compiler::CMake => {
+                let source_folder = get_full_path(&source_folder.to_path_buf()).ok()?;
+                Some(vec![
+                    vec![
+                        String::from("-S"),
+                        String::from(source_folder.to_str()?),
+                        String::from("-B"),
+                        build_folder.clone(),
+                    ],
+                    vec![String::from("--build"), build_folder],
+                ])
+            }
+            Compiler::Gcc | Compiler::Gxx => None,
+        }
+    }
+
+    /// Checks if `flag` is only meaningful when linking (libraries, linker options)
+    /// These flags are given to the link step only, the other flags are given to every step
+    pub fn is_link_flag(flag: &str) -> bool {
+        flag.starts_with("-l") || flag.starts_with("-L") || flag.starts_with("-Wl,")
+    }
+
     /// Collects the files in `files` that have an extension found in `allowed_extensions`
     fn collect_files_with_extension(
         files: &Vec<std::path::PathBuf>,
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+/// Represents the severity of a compiler diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+}
+
+impl DiagnosticLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        }
+    }
+}
+
+/// A compiler error or warning pointing to a location in an exo file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Parses a gcc/clang diagnostic line like `main.c:5:12: error: expected ';' before '}'`
+    /// ANSI colors are ignored, relative paths are resolved from `folder`
+    /// Returns None if the line isn't an error or a warning with a location
+    pub fn parse_line(line: &str, folder: &std::path::Path) -> Option<Self> {
+        let line = console::strip_ansi_codes(line);
+        let (level, marker_idx, marker_len) = [
+            (DiagnosticLevel::Error, ": error: "),
+            (DiagnosticLevel::Error, ": fatal error: "),
+            (DiagnosticLevel::Warning, ": warning: "),
+        ]
+        .iter()
+        .filter_map(|(level, marker)| Some((*level, line.find(marker)?, marker.len())))
+        .min_by_key(|(_, idx, _)| *idx)?;
+
+        // The location is `file:line:column`, the file can contain ':' on windows (C:\...)
+        let mut location = line[..marker_idx].rsplitn(3, ':');
+        let column = location.next()?.parse().ok()?;
+        let line_number = location.next()?.parse().ok()?;
+        let file = PathBuf::from(location.next()?);
+        let file = if file.is_relative() {
+            folder.join(file)
+        } else {
+            file
+        };
+        Some(Self {
+            level,
+            file,
+            line: line_number,
+            column,
+            message: String::from(line[marker_idx + marker_len..].trim()),
+        })
+    }
+
+    /// Collects every diagnostic found in a compilation output
+    pub fn parse_output(output: &[String], folder: &std::path::Path) -> Vec<Self> {
+        output
+            .iter()
+            .filter_map(|line| Diagnostic::parse_line(line, folder))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error() {
+        let diagnostic = Diagnostic::parse_line(
+            "/home/plx/exo/main.c:5:12: error: expected ';' before '}' token",
+            &PathBuf::from("exo"),
+        );
+        assert_eq!(
+            diagnostic,
+            Some(Diagnostic {
+                level: DiagnosticLevel::Error,
+                file: PathBuf::from("/home/plx/exo/main.c"),
+                line: 5,
+                column: 12,
+                message: String::from("expected ';' before '}' token"),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_colored_warning_with_relative_path() {
+        let line = "\u{1b}[01m\u{1b}[Kqueue.c:2:21:\u{1b}[m\u{1b}[K \u{1b}[01;35m\u{1b}[Kwarning: \u{1b}[m\u{1b}[Kunused parameter 'value' [\u{1b}[01;35m\u{1b}[K-Wunused-parameter\u{1b}[m\u{1b}[K]";
+        let diagnostic = Diagnostic::parse_line(line, &PathBuf::from("exo")).unwrap();
+        assert_eq!(diagnostic.level, DiagnosticLevel::Warning);
+        assert_eq!(diagnostic.file, PathBuf::from("exo").join("queue.c"));
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 21));
+        assert_eq!(
+            diagnostic.message,
+            "unused parameter 'value' [-Wunused-parameter]"
+        );
+    }
+
+    #[test]
+    fn parse_fatal_error() {
+        let diagnostic = Diagnostic::parse_line(
+            "main.c:1:10: fatal error: nope.h: No such file or directory",
+            &PathBuf::new(),
+        )
+        .unwrap();
+        assert_eq!(diagnostic.level, DiagnosticLevel::Error);
+        assert_eq!(diagnostic.message, "nope.h: No such file or directory");
+    }
+
+    #[test]
+    fn ignore_lines_without_location() {
+        let folder = PathBuf::new();
+        assert!(Diagnostic::parse_line("main.c: In function 'main':", &folder).is_none());
+        assert!(Diagnostic::parse_line("    5 |   return 0", &folder).is_none());
+        assert!(Diagnostic::parse_line("main.c:3:5: note: declared here", &folder).is_none());
+        assert!(Diagnostic::parse_line(
+            "/usr/bin/ld: main.o: in function `main': undefined reference to `f'",
+            &folder
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn parse_compilation_output() {
+        let output = vec![
+            String::from("main.c: In function 'main':"),
+            String::from("main.c:4:5: warning: unused variable 'a' [-Wunused-variable]"),
+            String::from("    4 |     int a;"),
+            String::from("main.c:6:1: error: expected ';' before '}' token"),
+        ];
+        let diagnostics = Diagnostic::parse_output(&output, &PathBuf::new());
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Warning);
+        assert_eq!(diagnostics[1].line, 6);
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::process::process_handler::command_exists;
+
+use super::compiler::Compiler;
+
+/// Commands used to build exos
+/// Each command can be a name found in the PATH (`clang`, `gcc-13`) or an absolute path
+/// See `UserConfig` to know how to change them
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(default)]
+pub struct Toolchain {
+    pub c: String,
+    pub cpp: String,
+    pub make: String,
+    pub cmake: String,
+}
+
+impl Default for Toolchain {
+    fn default() -> Self {
+        Self {
+            c: String::from("gcc"),
+            cpp: String::from("g++"),
+            make: String::from("make"),
+            cmake: String::from("cmake"),
+        }
+    }
+}
+
+impl Toolchain {
+    /// Returns the command configured for `compiler`
+    pub fn cmd(&self, compiler: &Compiler) -> &str {
+        match compiler {
+            Compiler::Gcc => &self.c,
+            Compiler::Gxx => &self.cpp,
+            Compiler::Make => &self.make,
+            Compiler::CMake => &self.cmake,
+        }
+    }
+
+    /// Checks that the command of every compiler in `compilers` can be launched
+    /// Returns a human readable error for each missing command
+    pub fn find_missing(&self, compilers: &[Compiler]) -> Vec<String> {
+        let mut checked: Vec<&str> = Vec::new();
+        let mut missing = Vec::new();
+        for compiler in compilers {
+            let cmd = self.cmd(compiler);
+            if checked.contains(&cmd) {
+                continue;
+            }
+            checked.push(cmd);
+            if !command_exists(cmd) {
+                missing.push(format!(
+                    "Compiler `{}` was not found, install it or change it in the PLX config file",
+                    cmd
+                ));
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::parser::toml_parser::toml_deserialize;
+
+    use super::*;
+
+    #[test]
+    fn default_toolchain_is_gnu() {
+        let toolchain = Toolchain::default();
+        assert_eq!(toolchain.cmd(&Compiler::Gcc), "gcc");
+        assert_eq!(toolchain.cmd(&Compiler::Gxx), "g++");
+        assert_eq!(toolchain.cmd(&Compiler::Make), "make");
+        assert_eq!(toolchain.cmd(&Compiler::CMake), "cmake");
+    }
+
+    #[test]
+    fn partial_toolchain_keeps_defaults() {
+        let toolchain: Toolchain =
+            toml_deserialize("c = 'clang'\ncpp = '/usr/bin/clang++'").unwrap();
+        assert_eq!(toolchain.cmd(&Compiler::Gcc), "clang");
+        assert_eq!(toolchain.cmd(&Compiler::Gxx), "/usr/bin/clang++");
+        assert_eq!(toolchain.cmd(&Compiler::Make), "make");
+    }
+
+    #[test]
+    fn missing_compiler_is_reported_once() {
+        let toolchain = Toolchain {
+            c: String::from("plx-missing-compiler"),
+            cpp: String::from("plx-missing-compiler"),
+            ..Default::default()
+        };
+        let missing = toolchain.find_missing(&[Compiler::Gcc, Compiler::Gxx, Compiler::Gcc]);
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].contains("`plx-missing-compiler`"));
+    }
+
+    #[test]
+    fn installed_compiler_is_not_reported() {
+        let toolchain = Toolchain::default();
+        assert!(toolchain.find_missing(&[Compiler::Gcc]).is_empty());
+    }
+}
@@ -18,3 +18,40 @@ pub fn get_default_editor() -> Option<String> {
         Err(_) => None,
     }
 }
+
+// Gets the arguments to open `file` at the given line and column in `editor`
+// Editors without known support only open the file
+pub fn get_goto_args(editor: &str, file: &str, line: usize, column: usize) -> Vec<String> {
+    match editor {
+        "code" | "codium" => vec![
+            String::from("--goto"),
+            format!("{}:{}:{}", file, line, column),
+        ],
+        "clion" => vec![
+            String::from("--line"),
+            line.to_string(),
+            String::from("--column"),
+            column.to_string(),
+            String::from(file),
+        ],
+        _ => vec![String::from(file)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goto_args() {
+        assert_eq!(
+            get_goto_args("code", "main.c", 5, 12),
+            vec!["--goto", "main.c:5:12"]
+        );
+        assert_eq!(
+            get_goto_args("clion", "main.c", 5, 12),
+            vec!["--line", "5", "--column", "12", "main.c"]
+        );
+        assert_eq!(get_goto_args("vim", "main.c", 5, 12), vec!["main.c"]);
+    }
+}
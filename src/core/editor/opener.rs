@@ -15,7 +15,7 @@ use crate::{
     models::event::Event,
 };
 
-use super::editor::get_default_editor;
+use super::editor::{get_default_editor, get_goto_args};
 
 // Editor Opener Worker
 pub struct EditorOpener {
@@ -41,6 +41,19 @@ impl EditorOpener {
             None => None,
         }
     }
+
+    // Tries to create self using the default editor, the file is opened at the given location
+    pub fn new_default_editor_at(
+        file_path: path::PathBuf,
+        line: usize,
+        column: usize,
+    ) -> Option<Self> {
+        let editor = get_default_editor()?;
+        let args = get_goto_args(&editor, file_path.to_str()?, line, column);
+        Some(EditorOpener {
+            runner: Runner::new(editor, args),
+        })
+    }
 }
 impl Work for EditorOpener {
     fn work_type(&self) -> WorkType {
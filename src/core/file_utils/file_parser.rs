@@ -18,6 +18,24 @@ pub enum ParseWarning {
     ExoFileNotFound(String),
     MultipleSolutionsFound(String),
     InvalidFileName(String),
+    IncompatibleLanguages(String),
+    UnlistedFolder(String),
+    DuplicateCheckName(String),
+    EmptyExpectedOutput(String),
+    DuplicateInstruction(String),
+    DuplicateHints(String),
+    UnknownRequirement(String),
+}
+
+impl ParseWarning {
+    /// Returns true if the problem makes part of the course unusable
+    /// e.g. a skill or an exo that couldn't be parsed and is missing from the course
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            ParseWarning::ParseSkillFail(_) | ParseWarning::ParseExoFail(_)
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -54,6 +72,23 @@ impl fmt::Display for ParseWarning {
                 write!(f, "Multiple Solutions Found ({})", detail)
             }
             ParseWarning::InvalidFileName(detail) => write!(f, "Invalid File Name ({})", detail),
+            ParseWarning::IncompatibleLanguages(detail) => {
+                write!(f, "Incompatible Languages ({})", detail)
+            }
+            ParseWarning::UnlistedFolder(detail) => write!(f, "Unlisted Folder ({})", detail),
+            ParseWarning::DuplicateCheckName(detail) => {
+                write!(f, "Duplicate Check Name ({})", detail)
+            }
+            ParseWarning::DuplicateInstruction(detail) => {
+                write!(f, "Duplicate Instruction ({})", detail)
+            }
+            ParseWarning::DuplicateHints(detail) => write!(f, "Duplicate Hints ({})", detail),
+            ParseWarning::UnknownRequirement(detail) => {
+                write!(f, "Unknown Requirement ({})", detail)
+            }
+            ParseWarning::EmptyExpectedOutput(detail) => {
+                write!(f, "Empty Expected Output ({})", detail)
+            }
         }
     }
 }
@@ -39,3 +39,54 @@ pub fn get_full_path(path: &std::path::PathBuf) -> Result<std::path::PathBuf, io
 pub fn current_folder() -> Result<std::path::PathBuf, io::Error> {
     std::env::current_dir()
 }
+
+/// Lists every file found in `dir` and its sub folders
+/// Folders whose name is contained in `ignored_folders` are not explored
+pub fn list_dir_files_recursive(
+    dir: &std::path::PathBuf,
+    ignored_folders: &[&str],
+) -> Result<Vec<std::path::PathBuf>, io::Error> {
+    let mut files = list_dir_files(dir)?;
+    for folder in list_dir_folders(dir)? {
+        let ignored = folder
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| ignored_folders.contains(&name));
+        if !ignored {
+            files.append(&mut list_dir_files_recursive(&folder, ignored_folders)?);
+        }
+    }
+    Ok(files)
+}
+
+/// Lists the folders of `dir` containing a `marker_file` whose name isn't in `listed`
+/// Useful to find skills or exos that were forgotten in course.toml or skill.toml
+pub fn find_unlisted_folders(
+    dir: &std::path::PathBuf,
+    listed: &[std::path::PathBuf],
+    marker_file: &str,
+) -> Vec<std::path::PathBuf> {
+    let mut folders: Vec<std::path::PathBuf> = list_dir_folders(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|folder| folder.join(marker_file).exists())
+        .filter(|folder| !listed.iter().any(|listed| dir.join(listed) == *folder))
+        .collect();
+    folders.sort();
+    folders
+}
+
+/// Checks if `file` can be executed
+/// On unix this checks the permission bits, on windows the `.exe` extension
+pub fn is_executable(file: &std::path::Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        file.is_file() && file.extension().is_some_and(|extension| extension == "exe")
+    }
+}
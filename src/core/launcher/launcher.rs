@@ -5,6 +5,7 @@ use std::{
         mpsc::{self, Sender},
         Arc,
     },
+    time::Duration,
 };
 
 use crate::{
@@ -32,6 +33,16 @@ impl Launcher {
             None
         }
     }
+    /// Stops the target binary if it runs longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.runner = self.runner.with_timeout(timeout);
+        self
+    }
+    /// Writes `stdin` to the standard input of the target binary
+    pub fn with_stdin(mut self, stdin: Option<String>) -> Self {
+        self.runner = self.runner.with_stdin(stdin);
+        self
+    }
     pub fn get_full_command(&self) -> String {
         self.runner.get_full_command()
     }
@@ -51,6 +62,10 @@ impl Work for Launcher {
                 }
                 RunEvent::ProcessCreated => tx.send(Event::RunStart(self.id)),
                 RunEvent::ProcessEnd(_) => tx.send(Event::RunEnd(self.id)),
+                RunEvent::ProcessTimedOut(timeout) => tx.send(Event::RunFail(
+                    self.id,
+                    format!("timed out after {}s", timeout.as_secs_f64()),
+                )),
                 RunEvent::ProcessNewOutputLine(line) => {
                     tx.send(Event::RunOutputLine(self.id, line))
                 }
@@ -1,3 +1,5 @@
+pub mod discovery;
 pub mod from_dir;
 pub mod object_creator;
+pub mod toml_editor;
 pub mod toml_parser;
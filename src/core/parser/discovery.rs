@@ -0,0 +1,98 @@
+use serde::Deserialize;
+
+use crate::core::file_utils::file_utils::find_unlisted_folders;
+
+use super::object_creator::create_object_from_file;
+
+/// The `order` that can be set in skill.toml and exo.toml to sort the discovered folders
+#[derive(Deserialize)]
+struct OrderInfo {
+    order: Option<u32>,
+}
+
+/// Finds the folders of `dir` containing a `marker_file`, for courses and skills using `discover`
+/// Folders are sorted by the `order` of their marker file, or else by the number prefixing their
+/// name like `02-pointers`, folders without any of them come last sorted by name
+/// Returned folders are relative to `dir`, like the folders listed in course.toml and skill.toml
+pub fn discover_folders(dir: &std::path::PathBuf, marker_file: &str) -> Vec<std::path::PathBuf> {
+    let mut folders: Vec<(Option<u32>, std::path::PathBuf)> =
+        find_unlisted_folders(dir, &[], marker_file)
+            .into_iter()
+            .filter_map(|folder| {
+                let name = std::path::PathBuf::from(folder.file_name()?);
+                // An invalid marker file is reported when the folder is parsed
+                let order = create_object_from_file::<OrderInfo>(&folder.join(marker_file))
+                    .ok()
+                    .and_then(|info| info.order)
+                    .or_else(|| number_prefix(&name));
+                Some((order, name))
+            })
+            .collect();
+    folders.sort_by(|(order_a, name_a), (order_b, name_b)| {
+        (order_a.is_none(), order_a, name_a).cmp(&(order_b.is_none(), order_b, name_b))
+    });
+    folders.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Returns the `listed` folders followed by the other folders of `dir` found by
+/// `discover_folders`
+pub fn add_discovered_folders(
+    dir: &std::path::PathBuf,
+    listed: &[std::path::PathBuf],
+    marker_file: &str,
+) -> Vec<std::path::PathBuf> {
+    let mut folders = listed.to_vec();
+    for folder in discover_folders(dir, marker_file) {
+        if !listed
+            .iter()
+            .any(|listed| dir.join(listed) == dir.join(&folder))
+        {
+            folders.push(folder);
+        }
+    }
+    folders
+}
+
+/// Returns the number at the start of `name`, like 2 for `02-pointers`
+fn number_prefix(name: &std::path::Path) -> Option<u32> {
+    let name = name.to_str()?;
+    let digits = name.chars().take_while(char::is_ascii_digit).count();
+    name[..digits].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn folders_are_sorted_by_order_then_prefix() {
+        let dir = PathBuf::from("examples/mock-plx-project/mock-skills/ordered-exos");
+
+        assert_eq!(
+            discover_folders(&dir, "exo.toml"),
+            vec![
+                PathBuf::from("pointers"),
+                PathBuf::from("2-strings"),
+                PathBuf::from("arrays"),
+                PathBuf::from("10-files"),
+                PathBuf::from("basics"),
+            ]
+        );
+        assert_eq!(
+            add_discovered_folders(
+                &dir,
+                &[PathBuf::from("basics/"), PathBuf::from("arrays")],
+                "exo.toml"
+            ),
+            vec![
+                PathBuf::from("basics/"),
+                PathBuf::from("arrays"),
+                PathBuf::from("pointers"),
+                PathBuf::from("2-strings"),
+                PathBuf::from("10-files"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+use crate::core::file_utils::file_parser::ParseError;
+
+/// Rewrites the `expected` output of some checks of an exo.toml
+/// `expected` contains the index of each check to change and its new expected output
+/// Comments and formatting of the rest of the file are kept as is
+pub fn set_checks_expected(
+    content: &str,
+    expected: &[(usize, String)],
+) -> Result<String, ParseError> {
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|err| ParseError::ParseError(err.to_string()))?;
+    for (check_idx, output) in expected {
+        let item = document
+            .get_mut("checks")
+            .and_then(|checks| checks.get_mut(*check_idx))
+            .and_then(|check| check.get_mut("test"))
+            .and_then(|test| test.get_mut("expected"))
+            .ok_or(ParseError::ParseError(format!(
+                "Couldn't find the expected output of check {}",
+                check_idx
+            )))?;
+        replace_value(item, output);
+    }
+    Ok(document.to_string())
+}
+
+/// Replaces the expected output of rows in the `cases` table of checks, `expected` contains the
+/// index of the check, the index of the row and the new output
+pub fn set_cases_expected(
+    content: &str,
+    expected: &[(usize, usize, String)],
+) -> Result<String, ParseError> {
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|err| ParseError::ParseError(err.to_string()))?;
+    for (check_idx, case_idx, output) in expected {
+        let item = document
+            .get_mut("checks")
+            .and_then(|checks| checks.get_mut(*check_idx))
+            .and_then(|check| check.get_mut("cases"))
+            .and_then(|cases| cases.get_mut(*case_idx))
+            .and_then(|case| case.get_mut("expected"))
+            .ok_or(ParseError::ParseError(format!(
+                "Couldn't find the expected output of case {} of check {}",
+                case_idx, check_idx
+            )))?;
+        replace_value(item, output);
+    }
+    Ok(document.to_string())
+}
+
+/// Replaces the string in `item` by `output`, keeping the spaces around the old value
+fn replace_value(item: &mut Item, output: &str) {
+    let mut value = Value::from(output);
+    if let Some(old) = item.as_value() {
+        *value.decor_mut() = old.decor().clone();
+    }
+    *item = Item::Value(value);
+}
+
+/// Adds `value` at the end of the array `key`, e.g. a new folder in the `skills` of a course.toml
+/// The array is created if it doesn't exist, nothing changes if it already contains `value`
+pub fn append_to_array(content: &str, key: &str, value: &str) -> Result<String, ParseError> {
+    let mut document = content
+        .parse::<DocumentMut>()
+        .map_err(|err| ParseError::ParseError(err.to_string()))?;
+    let array = document
+        .entry(key)
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or(ParseError::ParseError(format!("{} is not an array", key)))?;
+    if !array.iter().any(|item| item.as_str() == Some(value)) {
+        array.push(value);
+    }
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_test_expected_is_replaced() {
+        let content = "# Comment kept\nname = 'Exo'\n[[checks]]\nname = 'A'\ntest = { type = 'output', expected = 'old' }\n[[checks]]\nname = 'B' # B\ntest = { type = 'output', expected = 'kept' }\n";
+        let edited = set_checks_expected(content, &[(0, String::from("new"))]).unwrap();
+        assert_eq!(
+            edited,
+            "# Comment kept\nname = 'Exo'\n[[checks]]\nname = 'A'\ntest = { type = 'output', expected = \"new\" }\n[[checks]]\nname = 'B' # B\ntest = { type = 'output', expected = 'kept' }\n"
+        );
+    }
+
+    #[test]
+    fn multiline_expected_in_test_table() {
+        let content = "name = 'Exo'\n[[checks]]\nname = 'A'\n[checks.test]\ntype = 'output'\nexpected = 'old'\n";
+        let edited = set_checks_expected(content, &[(0, String::from("line 1\nline 2"))]).unwrap();
+        let parsed: toml::Table = toml::from_str(&edited).unwrap();
+        assert_eq!(
+            parsed["checks"][0]["test"]["expected"].as_str(),
+            Some("line 1\nline 2")
+        );
+        assert!(edited.contains("\"\"\"\nline 1\nline 2\"\"\""));
+    }
+
+    #[test]
+    fn case_expected_is_replaced() {
+        let content = "name = 'Exo'\n[[checks]]\nname = 'Sum'\ncases = [\n  { args = ['1', '2'], expected = '3' },\n  { args = ['2', '2'], expected = 'old' },\n]\n";
+        let edited = set_cases_expected(content, &[(0, 1, String::from("4"))]).unwrap();
+        assert_eq!(
+            edited,
+            "name = 'Exo'\n[[checks]]\nname = 'Sum'\ncases = [\n  { args = ['1', '2'], expected = '3' },\n  { args = ['2', '2'], expected = \"4\" },\n]\n"
+        );
+        assert!(set_cases_expected(content, &[(0, 2, String::from("4"))]).is_err());
+    }
+
+    #[test]
+    fn folder_is_appended_once() {
+        let content = "name = 'Intro' # Skill\nexos = ['basic-args']\n";
+        let edited = append_to_array(content, "exos", "pointers").unwrap();
+        assert_eq!(
+            edited,
+            "name = 'Intro' # Skill\nexos = ['basic-args', \"pointers\"]\n"
+        );
+        assert_eq!(
+            append_to_array(&edited, "exos", "pointers").unwrap(),
+            edited
+        );
+    }
+
+    #[test]
+    fn missing_array_is_created() {
+        let edited = append_to_array("name = 'Course'\n", "skills", "intro").unwrap();
+        assert_eq!(edited, "name = 'Course'\nskills = [\"intro\"]\n");
+        assert!(append_to_array("skills = 'intro'\n", "skills", "basics").is_err());
+    }
+
+    #[test]
+    fn missing_check_is_an_error() {
+        let content = "name = 'Exo'\n";
+        assert!(set_checks_expected(content, &[(0, String::from("new"))]).is_err());
+    }
+}
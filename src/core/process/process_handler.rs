@@ -23,8 +23,17 @@ pub enum ProcessStatus {
 /// Launches a sub process `cmd` using `args`
 /// Stdout and stderr are piped and can then be retrieved using the Child returned
 /// eg: child.stdout.take() and child.stderr.take()
-pub fn spawn_process(cmd: &str, args: Vec<String>) -> Result<Child, ProcessError> {
-    let child = Command::new(OsStr::new(&cmd))
+/// With `pipe_stdin`, stdin is also piped so it can be written with child.stdin.take()
+pub fn spawn_process(
+    cmd: &str,
+    args: Vec<String>,
+    pipe_stdin: bool,
+) -> Result<Child, ProcessError> {
+    let mut command = Command::new(OsStr::new(&cmd));
+    if pipe_stdin {
+        command.stdin(Stdio::piped());
+    }
+    let child = command
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -33,6 +42,17 @@ pub fn spawn_process(cmd: &str, args: Vec<String>) -> Result<Child, ProcessError
 
     Ok(child)
 }
+/// Checks if `cmd` can be launched by running `cmd --version`
+/// `cmd` can be a program name found in the PATH or a path to an executable
+pub fn command_exists(cmd: &str) -> bool {
+    Command::new(OsStr::new(cmd))
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
 /// Polls the process status without blocking
 /// Can be called in a loop as it will handling sleep so it doesn't use 100% of the cpu
 /// This function may never return ProcessStatus::Done if it blocks waiting for stdin
@@ -1,7 +1,7 @@
 use crate::core::process::process_handler::{self, ProcessStatus};
 use log::error;
 use std::{
-    io::{BufRead, BufReader, Read},
+    io::{BufRead, BufReader, Read, Write},
     process::ExitStatus,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -9,7 +9,7 @@ use std::{
         Arc,
     },
     thread::{self, sleep, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 #[derive(Debug, PartialEq)]
@@ -18,16 +18,39 @@ pub enum RunEvent {
     ProcessCreated,
     ProcessEnd(bool),
     ProcessNewOutputLine(String),
+    // The process was stopped because it ran longer than the timeout, sent instead of ProcessEnd
+    ProcessTimedOut(Duration),
 }
 // A wrapper for running process and handling process events
 pub struct Runner {
     command: String,
     args: Vec<String>,
+    timeout: Option<Duration>,
+    stdin: Option<String>,
 }
 
 impl Runner {
     pub fn new(command: String, args: Vec<String>) -> Self {
-        Self { command, args }
+        Self {
+            command,
+            args,
+            timeout: None,
+            stdin: None,
+        }
+    }
+    // Writes `stdin` to the standard input of the process, which is closed after it
+    // Without it, the process reads the stdin of PLX
+    pub fn with_stdin(mut self, stdin: Option<String>) -> Self {
+        self.stdin = stdin;
+        self
+    }
+    // Stops the process if it runs longer than `timeout`, None waits until it ends
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    pub fn get_command(&self) -> &str {
+        &self.command
     }
     pub fn get_full_command(&self) -> String {
         format!("{} {}", &self.command, &self.args.join(" "))
@@ -57,13 +80,22 @@ impl Runner {
         tx: Sender<RunEvent>,
         should_stop: Arc<AtomicBool>,
     ) -> Result<ExitStatus, ()> {
-        let mut process = process_handler::spawn_process(&self.command, self.args.clone())
-            .map_err(|err| {
-                let _ = tx.send(RunEvent::ProcessCreationFailed(format!("{:?}", err)));
-            })?;
+        let mut process =
+            process_handler::spawn_process(&self.command, self.args.clone(), self.stdin.is_some())
+                .map_err(|err| {
+                    let _ = tx.send(RunEvent::ProcessCreationFailed(format!("{:?}", err)));
+                })?;
 
         let _ = tx.send(RunEvent::ProcessCreated);
 
+        // Write stdin from another thread, the process may not read it before its output is read
+        if let (Some(mut stdin), Some(content)) = (process.stdin.take(), self.stdin.clone()) {
+            thread::spawn(move || {
+                // The process can exit without reading its input, this is not an error
+                let _ = stdin.write_all(content.as_bytes());
+            });
+        }
+
         // Take stdout and stderr and launch a stream reader for each
         let mut stdout_thread = {
             if let Some(stdout) = process.stdout.take() {
@@ -80,9 +112,14 @@ impl Runner {
             }
         };
 
-        // Loop forever until we either get asked to stop or the process ends
+        // Loop forever until we either get asked to stop, the process ends or times out
+        let start = Instant::now();
+        let mut timed_out = None;
         let exit_status = loop {
-            if should_stop.load(Ordering::Relaxed) {
+            if timed_out.is_none() {
+                timed_out = self.timeout.filter(|timeout| start.elapsed() > *timeout);
+            }
+            if should_stop.load(Ordering::Relaxed) || timed_out.is_some() {
                 if process_handler::stop_child(&mut process).is_err() {
                     error!("Couldn't kill child process");
                     break None;
@@ -113,7 +150,10 @@ impl Runner {
             false
         };
 
-        let _ = tx.send(RunEvent::ProcessEnd(success));
+        let _ = tx.send(match timed_out {
+            Some(timeout) => RunEvent::ProcessTimedOut(timeout),
+            None => RunEvent::ProcessEnd(success),
+        });
         exit_status.ok_or(())
     }
 }
@@ -241,4 +281,50 @@ mod test {
             .expect("Couldn't get child exit status");
         let _ = std::fs::remove_file(target);
     }
+
+    #[test]
+    #[timeout(5000)]
+    fn test_timeout_stops_program() {
+        if cfg!(windows) {
+            return;
+        };
+        // This code does while(1)
+        let c_file = "./examples/basics/c/infinite_loop.c";
+        let target = "./target/infinite_loop_timeout";
+        compile_program(c_file, target);
+
+        let runner =
+            Runner::new(target.to_string(), vec![]).with_timeout(Some(Duration::from_millis(500)));
+        let (tx, rx) = channel();
+        let _ = runner.run(tx, Arc::new(AtomicBool::new(false)));
+        let last_event = rx.try_iter().last();
+        let _ = std::fs::remove_file(target);
+        assert_eq!(
+            last_event,
+            Some(RunEvent::ProcessTimedOut(Duration::from_millis(500)))
+        );
+    }
+
+    #[test]
+    #[timeout(5000)]
+    fn test_stdin_is_written() {
+        if cfg!(windows) {
+            return;
+        };
+        let runner =
+            Runner::new(String::from("cat"), vec![]).with_stdin(Some(String::from("a\nb")));
+        let (tx, rx) = channel();
+        assert!(runner.run(tx, Arc::new(AtomicBool::new(false))).is_ok());
+        let lines: Vec<RunEvent> = rx
+            .try_iter()
+            .filter(|event| matches!(event, RunEvent::ProcessNewOutputLine(_)))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                RunEvent::ProcessNewOutputLine(String::from("a")),
+                RunEvent::ProcessNewOutputLine(String::from("b"))
+            ]
+        );
+    }
 }
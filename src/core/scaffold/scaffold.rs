@@ -0,0 +1,219 @@
+use core::fmt;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, DocumentMut};
+
+use crate::{
+    core::{
+        file_utils::file_utils::{read_file, write_file},
+        parser::toml_editor::append_to_array,
+    },
+    models::constants::{COURSE_INFO_FILE, EXO_INFO_FILE, SKILL_INFO_FILE},
+};
+
+/// Output printed by the starter exo and checked by its sample check
+const SAMPLE_OUTPUT: &str = "Hello, PLX!";
+
+/// Represents an error while creating a course, a skill or an exo
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The folder to create already exists
+    AlreadyExists(PathBuf),
+    /// No parent course or skill was found, contains the info file searched
+    ParentNotFound(&'static str),
+    /// The name doesn't contain any character usable in a folder name
+    InvalidName(String),
+    /// The parent info file couldn't be updated
+    ParentNotUpdated(PathBuf, String),
+    /// A file or folder couldn't be created
+    Io(String),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(folder) => write!(f, "{:?} already exists", folder),
+            ScaffoldError::ParentNotFound(info_file) => {
+                write!(
+                    f,
+                    "No {} found in the current folder or its parents",
+                    info_file
+                )
+            }
+            ScaffoldError::InvalidName(name) => {
+                write!(f, "Couldn't create a folder name from {:?}", name)
+            }
+            ScaffoldError::ParentNotUpdated(file, err) => {
+                write!(f, "Couldn't update {:?} ({})", file, err)
+            }
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<std::io::Error> for ScaffoldError {
+    fn from(err: std::io::Error) -> Self {
+        ScaffoldError::Io(err.to_string())
+    }
+}
+
+/// Returns the folder name used for `name`: lowercase, words separated by dashes
+/// e.g. `Pointers & arrays` becomes `pointers-arrays`
+pub fn folder_name(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds the closest folder containing `info_file`, starting from `path`
+fn find_parent(path: &Path, info_file: &'static str) -> Result<PathBuf, ScaffoldError> {
+    let path = std::path::absolute(path)?;
+    path.ancestors()
+        .find(|folder| folder.join(info_file).is_file())
+        .map(Path::to_path_buf)
+        .ok_or(ScaffoldError::ParentNotFound(info_file))
+}
+
+/// Builds the content of an info file with its `name` and an empty list of children
+fn info_file(name: &str, children_key: &str) -> String {
+    let mut document = DocumentMut::new();
+    document["name"] = value(name);
+    document[children_key] = value(toml_edit::Array::new());
+    document.to_string()
+}
+
+/// Creates the folder of `name` inside `parent` and writes its files, then registers the folder
+/// in the `children_key` list of the parent info file
+/// The parent is only modified once the new folder is complete
+fn create_child(
+    parent: &Path,
+    parent_info_file: &str,
+    children_key: &str,
+    name: &str,
+    files: impl FnOnce(&Path) -> Result<(), ScaffoldError>,
+) -> Result<PathBuf, ScaffoldError> {
+    let child = folder_name(name);
+    if child.is_empty() {
+        return Err(ScaffoldError::InvalidName(String::from(name)));
+    }
+    let folder = parent.join(&child);
+    if folder.exists() {
+        return Err(ScaffoldError::AlreadyExists(folder));
+    }
+    let parent_file = parent.join(parent_info_file);
+    let not_updated = |err: String| ScaffoldError::ParentNotUpdated(parent_file.clone(), err);
+    let parent_content = read_file(&parent_file).map_err(|err| not_updated(err.to_string()))?;
+    let parent_content = append_to_array(&parent_content, children_key, &child)
+        .map_err(|err| not_updated(format!("{:?}", err)))?;
+
+    std::fs::create_dir(&folder)?;
+    if let Err(err) = files(&folder) {
+        let _ = std::fs::remove_dir_all(&folder);
+        return Err(err);
+    }
+    write_file(&parent_file, &parent_content).map_err(|err| not_updated(err.to_string()))?;
+    Ok(folder)
+}
+
+/// Creates a new course folder named after `name` inside `parent`, without any skill
+pub fn new_course(parent: &Path, name: &str) -> Result<PathBuf, ScaffoldError> {
+    let course = folder_name(name);
+    if course.is_empty() {
+        return Err(ScaffoldError::InvalidName(String::from(name)));
+    }
+    let folder = parent.join(course);
+    if folder.exists() {
+        return Err(ScaffoldError::AlreadyExists(folder));
+    }
+    std::fs::create_dir_all(&folder)?;
+    write_file(&folder.join(COURSE_INFO_FILE), &info_file(name, "skills"))?;
+    Ok(folder)
+}
+
+/// Creates a new skill in the course containing `path` and adds it to the course skills
+pub fn new_skill(path: &Path, name: &str) -> Result<PathBuf, ScaffoldError> {
+    let course = find_parent(path, COURSE_INFO_FILE)?;
+    create_child(&course, COURSE_INFO_FILE, "skills", name, |folder| {
+        write_file(&folder.join(SKILL_INFO_FILE), &info_file(name, "exos"))?;
+        Ok(())
+    })
+}
+
+/// Creates a new C exo in the skill containing `path` and adds it to the skill exos
+/// The exo has a starter `main.c`, its solution `main.sol.c` and a sample check
+pub fn new_exo(path: &Path, name: &str) -> Result<PathBuf, ScaffoldError> {
+    let skill = find_parent(path, SKILL_INFO_FILE)?;
+    create_child(&skill, SKILL_INFO_FILE, "exos", name, |folder| {
+        let mut exo_info = DocumentMut::new();
+        exo_info["name"] = value(name);
+        exo_info["instruction"] = value(format!("Print `{}`", SAMPLE_OUTPUT));
+        let mut exo_info = exo_info.to_string();
+        exo_info.push_str(&format!(
+            "\n[[checks]]\nname = \"Prints the greeting\"\ntest = {{ type = \"output\", expected = \"{}\" }}\n",
+            SAMPLE_OUTPUT
+        ));
+        write_file(&folder.join(EXO_INFO_FILE), &exo_info)?;
+        write_file(
+            &folder.join("main.c"),
+            "#include <stdio.h>\n\nint main(int argc, char *argv[]) {\n  // TODO\n}\n",
+        )?;
+        write_file(
+            &folder.join("main.sol.c"),
+            &format!(
+                "#include <stdio.h>\n\nint main(int argc, char *argv[]) {{\n  printf(\"{}\\n\");\n}}\n",
+                SAMPLE_OUTPUT
+            ),
+        )?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::parser::from_dir::FromDir,
+        models::{exo::Exo, project::Project},
+    };
+
+    use super::*;
+
+    #[test]
+    fn folder_names_are_slugs() {
+        assert_eq!(folder_name("Pointers & arrays"), "pointers-arrays");
+        assert_eq!(folder_name("  Basic args "), "basic-args");
+        assert_eq!(folder_name("???"), "");
+    }
+
+    #[test]
+    fn new_course_can_be_parsed() {
+        let parent = tempfile::tempdir().unwrap();
+        let course = new_course(parent.path(), "My \"C\" course").unwrap();
+        assert_eq!(course, parent.path().join("my-c-course"));
+        let skill = new_skill(&course, "Pointers").unwrap();
+        let exo = new_exo(&skill, "First pointer").unwrap();
+        assert_eq!(exo, course.join("pointers").join("first-pointer"));
+        assert!(matches!(
+            new_exo(&skill, "First pointer"),
+            Err(ScaffoldError::AlreadyExists(_))
+        ));
+
+        let (project, warnings) = Project::from_dir(&course).unwrap();
+        assert_eq!(warnings, vec![]);
+        assert_eq!(project.name, "My \"C\" course");
+        assert_eq!(project.skills[0].exos[0].name, "First pointer");
+        let (exo, _) = Exo::from_dir(&exo).unwrap();
+        assert_eq!(exo.solutions.len(), 1);
+        assert_eq!(exo.checks.len(), 1);
+    }
+
+    #[test]
+    fn exo_needs_a_skill() {
+        let folder = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            new_exo(folder.path(), "Exo"),
+            Err(ScaffoldError::ParentNotFound(SKILL_INFO_FILE))
+        ));
+    }
+}
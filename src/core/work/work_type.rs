@@ -9,4 +9,5 @@ pub enum WorkType {
     Launcher,
     Checker,
     Watcher,
+    Grader,
 }
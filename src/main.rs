@@ -1,22 +1,126 @@
-use crate::app::app::App;
+use crate::{
+    app::{app::App, headless::Headless},
+    cli::{Cli, Command, NewCommand},
+    core::scaffold::scaffold::{self, ScaffoldError},
+};
+use clap::Parser;
 use simplelog::*;
-use std::fs::File;
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 pub mod app;
+pub mod cli;
 pub mod core;
 pub mod models;
 pub mod ui;
 
-fn main() {
+/// Exit code used when PLX couldn't start, e.g. when the course can't be parsed
+const STARTUP_FAILURE: u8 = 2;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
     WriteLogger::init(
-        LevelFilter::Trace,
+        cli.log_level,
         Config::default(),
-        File::create("debug.log").expect("Failed to create log file"),
+        File::create(&cli.log_file).expect("Failed to create log file"),
     )
     .expect("Failed to initialize WriteLogger");
-    match App::new() {
-        Ok(app) => app.run_forever(),
+
+    let success = match &cli.command {
+        None | Some(Command::Tui) => return start_tui(&cli),
+        Some(Command::Check {
+            path,
+            report,
+            output,
+            filter,
+        }) => {
+            // The report is written relative to where plx was launched, not to the course
+            let output = output
+                .as_ref()
+                .and_then(|file| std::path::absolute(file).ok());
+            load_course(&cli.target(path))
+                .map(|h| h.with_filter(filter.into()).check(*report, output.as_ref()))
+        }
+        Some(Command::VerifySolutions { path }) => {
+            load_course(&cli.target(path)).map(|h| h.verify_solutions())
+        }
+        Some(Command::Bless { path, dry_run }) => {
+            load_course(&cli.target(&Some(path.clone()))).map(|h| h.bless(*dry_run))
+        }
+        Some(Command::Grade {
+            path,
+            submissions,
+            format,
+            output,
+            jobs,
+        }) => {
+            // Paths given to plx are relative to where it was launched, not to the course
+            let submissions = std::path::absolute(submissions).unwrap_or(submissions.clone());
+            let output = output
+                .as_ref()
+                .and_then(|file| std::path::absolute(file).ok());
+            let jobs = jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(1, |jobs| jobs.get())
+            });
+            load_course(&cli.target(path))
+                .map(|h| h.grade(&submissions, jobs, *format, output.as_ref()))
+        }
+        Some(Command::New { what }) => Some(create(&cli, what)),
+        Some(Command::List { json, filter }) => {
+            load_course(&cli.course_folder()).map(|h| h.with_filter(filter.into()).list(*json))
+        }
+        Some(Command::Validate) => Some(Headless::validate(&cli.course_folder())),
+        Some(Command::Reset { path }) => {
+            load_course(&cli.target(&Some(path.clone()))).map(|h| h.reset())
+        }
+    };
+    match success {
+        Some(true) => ExitCode::SUCCESS,
+        Some(false) => ExitCode::FAILURE,
+        None => ExitCode::from(STARTUP_FAILURE),
+    }
+}
+
+fn start_tui(cli: &Cli) -> ExitCode {
+    match App::new(&cli.course_folder()) {
+        Ok(app) => {
+            app.run_forever();
+            ExitCode::SUCCESS
+        }
         Err(err) => {
             eprintln!("Error starting plx {err}");
+            ExitCode::from(STARTUP_FAILURE)
+        }
+    }
+}
+
+/// Creates a course, a skill or an exo and prints the created folder
+fn create(cli: &Cli, what: &NewCommand) -> bool {
+    let created: Result<PathBuf, ScaffoldError> = match what {
+        NewCommand::Course { name } => scaffold::new_course(Path::new("."), name),
+        NewCommand::Skill { name } => scaffold::new_skill(&cli.course_folder(), name),
+        NewCommand::Exo { name, skill } => match skill {
+            Some(skill) => scaffold::new_exo(&cli.target(&Some(skill.clone())), name),
+            None => scaffold::new_exo(Path::new("."), name),
+        },
+    };
+    match created {
+        Ok(folder) => {
+            println!("Created {}", folder.display());
+            true
+        }
+        Err(err) => {
+            eprintln!("Error: {err}");
+            false
         }
     }
 }
+
+/// Loads the course containing `target` for the commands that don't need the TUI
+fn load_course(target: &Path) -> Option<Headless> {
+    Headless::new(target)
+        .inspect_err(|err| eprintln!("Error starting plx {err}"))
+        .ok()
+}
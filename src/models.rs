@@ -3,8 +3,13 @@ pub mod check_state;
 pub mod constants;
 pub mod event;
 pub mod exo;
+pub mod exo_defaults;
+pub mod exo_metadata;
 pub mod exo_state;
+pub mod grade;
 pub mod key;
+pub mod language;
 pub mod project;
 pub mod skill;
 pub mod ui_state;
+pub mod user_config;
@@ -1,5 +1,9 @@
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::file_utils::{file_parser::ParseError, file_utils::read_file};
+
 /// Represents a Exo Check
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Check {
@@ -8,7 +12,15 @@ pub struct Check {
     #[serde(default)]
     pub args: Vec<String>,
 
+    /// Text written to the standard input of the program
+    #[serde(default)]
+    pub stdin: Option<String>,
+
     pub test: CheckTest,
+
+    /// Where the expected output was defined, see `plx bless`
+    #[serde(skip)]
+    pub source: ExpectedSource,
 }
 
 /// Represents the actual check type
@@ -18,3 +30,309 @@ pub enum CheckTest {
     #[serde(alias = "output")]
     Output { expected: String },
 }
+
+/// Where the expected output of a check is written, to update it from the solution output
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub enum ExpectedSource {
+    /// The `expected` field of the check at this index in the `checks` of exo.toml
+    Check(usize),
+    /// The `expected` field of a row in the `cases` table of a check of exo.toml, with the
+    /// indexes of the check and the row
+    Case(usize, usize),
+    /// A file inside the exo folder
+    File(PathBuf),
+    /// A check template of skill.toml or course.toml
+    #[default]
+    Inherited,
+}
+
+/// A check as written in exo.toml, skill.toml or course.toml
+/// Inputs and expected outputs can be read from files, see `CheckInfo::resolve`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct CheckInfo {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub stdin: Option<String>,
+    /// File written to the standard input, takes precedence over `stdin`
+    pub stdin_file: Option<PathBuf>,
+    /// Cases expanded in one check each, see `CheckCases`
+    pub cases: Option<CheckCases>,
+    pub test: Option<CheckTestInfo>,
+}
+
+/// Cases of a check, each one becomes a check named after the check and the case
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(untagged)]
+pub enum CheckCases {
+    /// Glob of input files like `tests/*.in`, each one is a case expecting the output written in
+    /// the `.out` file of the same name
+    Files(String),
+    /// Table with the inputs and expected output of each case
+    Table(Vec<CheckCase>),
+}
+
+/// One row of a cases table, the args and stdin of the check are used when not given
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct CheckCase {
+    pub name: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub stdin: Option<String>,
+    pub expected: String,
+}
+
+/// The test of a check as written in the info files
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type")]
+pub enum CheckTestInfo {
+    #[serde(alias = "output")]
+    Output {
+        expected: Option<String>,
+        /// File containing the expected output, takes precedence over `expected`
+        expected_file: Option<PathBuf>,
+    },
+}
+
+impl CheckInfo {
+    /// Builds the checks described by this info, reading their files inside `exo_folder`
+    /// `index` is the position of the check in exo.toml, None for inherited checks
+    pub fn resolve(
+        &self,
+        exo_folder: &Path,
+        index: Option<usize>,
+    ) -> Result<Vec<Check>, ParseError> {
+        let stdin = match &self.stdin_file {
+            Some(file) => Some(CheckInfo::read(&exo_folder.join(file))?),
+            None => self.stdin.clone(),
+        };
+        match &self.cases {
+            Some(CheckCases::Files(pattern)) => return self.resolve_files(exo_folder, pattern),
+            Some(CheckCases::Table(cases)) => return Ok(self.resolve_table(cases, stdin, index)),
+            None => {}
+        }
+        let (expected, source) = match &self.test {
+            Some(CheckTestInfo::Output {
+                expected_file: Some(file),
+                ..
+            }) => {
+                let file = exo_folder.join(file);
+                (CheckInfo::read(&file)?, ExpectedSource::File(file))
+            }
+            Some(CheckTestInfo::Output {
+                expected: Some(expected),
+                ..
+            }) => (
+                expected.clone(),
+                index.map_or(ExpectedSource::Inherited, ExpectedSource::Check),
+            ),
+            _ => {
+                return Err(ParseError::ParseError(format!(
+                    "Check {:?} has no expected output",
+                    self.name
+                )))
+            }
+        };
+        Ok(vec![Check {
+            name: self.name.clone(),
+            args: self.args.clone(),
+            stdin,
+            test: CheckTest::Output { expected },
+            source,
+        }])
+    }
+
+    /// Creates one check per row of the table, named after the row or its number
+    fn resolve_table(
+        &self,
+        cases: &[CheckCase],
+        stdin: Option<String>,
+        index: Option<usize>,
+    ) -> Vec<Check> {
+        cases
+            .iter()
+            .enumerate()
+            .map(|(case_idx, case)| Check {
+                name: match &case.name {
+                    Some(name) => format!("{} ({})", self.name, name),
+                    None => format!("{} #{}", self.name, case_idx + 1),
+                },
+                args: case.args.clone().unwrap_or_else(|| self.args.clone()),
+                stdin: case.stdin.clone().or_else(|| stdin.clone()),
+                test: CheckTest::Output {
+                    expected: case.expected.clone(),
+                },
+                source: index.map_or(ExpectedSource::Inherited, |check_idx| {
+                    ExpectedSource::Case(check_idx, case_idx)
+                }),
+            })
+            .collect()
+    }
+
+    /// Creates one check per input file matching `pattern`, sorted by file name
+    fn resolve_files(&self, exo_folder: &Path, pattern: &str) -> Result<Vec<Check>, ParseError> {
+        let folder = glob::Pattern::escape(&exo_folder.to_string_lossy());
+        let full_pattern = Path::new(&folder).join(pattern);
+        let mut inputs: Vec<PathBuf> = glob::glob(&full_pattern.to_string_lossy())
+            .map_err(|err| ParseError::ParseError(format!("Invalid cases {:?}: {}", pattern, err)))?
+            .filter_map(Result::ok)
+            .collect();
+        if inputs.is_empty() {
+            return Err(ParseError::FileNotFound(format!(
+                "No file matches the cases {:?} of check {:?}",
+                pattern, self.name
+            )));
+        }
+        inputs.sort();
+        inputs
+            .into_iter()
+            .map(|input| {
+                let expected_file = input.with_extension("out");
+                let case = input.file_stem().unwrap_or_default().to_string_lossy();
+                Ok(Check {
+                    name: format!("{} ({})", self.name, case),
+                    args: self.args.clone(),
+                    stdin: Some(CheckInfo::read(&input)?),
+                    test: CheckTest::Output {
+                        expected: CheckInfo::read(&expected_file)?,
+                    },
+                    source: ExpectedSource::File(expected_file),
+                })
+            })
+            .collect()
+    }
+
+    fn read(file: &Path) -> Result<String, ParseError> {
+        read_file(&file.to_path_buf())
+            .map_err(|err| ParseError::ReadFileError(format!("{:?}: {}", file, err)))
+    }
+}
+
+/// How the output of a program is compared to the expected output
+/// Whitespace at the end of the lines is always ignored
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Comparison {
+    /// Lines must be identical
+    #[default]
+    Exact,
+    /// Sequences of spaces and tabs count as a single space, empty lines are ignored
+    IgnoreWhitespace,
+    /// Letters are compared without their case
+    IgnoreCase,
+}
+
+impl Comparison {
+    /// Transforms `text` so that texts considered equal by this comparison become identical
+    pub fn normalize(&self, text: &str) -> String {
+        match self {
+            Comparison::Exact => String::from(text),
+            Comparison::IgnoreWhitespace => text
+                .lines()
+                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Comparison::IgnoreCase => text.to_lowercase(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::parser::from_dir::FromDir, models::exo::Exo};
+
+    use super::*;
+
+    #[test]
+    fn comparisons_normalize_output() {
+        let output = "Hello  World\n\n\tBye ";
+        assert_eq!(Comparison::Exact.normalize(output), output);
+        assert_eq!(
+            Comparison::IgnoreWhitespace.normalize(output),
+            "Hello World\nBye"
+        );
+        assert_eq!(
+            Comparison::IgnoreCase.normalize(output),
+            "hello  world\n\n\tbye "
+        );
+    }
+
+    fn info(toml: &str) -> CheckInfo {
+        crate::core::parser::toml_parser::toml_deserialize(toml).unwrap()
+    }
+
+    /// Exo reading its inputs and expected outputs from a `tests` folder
+    const TEST_FILES_EXO: &str = "examples/mock-plx-project/mock-skill/test-files";
+
+    #[test]
+    fn files_are_read_in_the_exo_folder() {
+        let folder = Path::new(TEST_FILES_EXO);
+        let checks = info("name = 'Double'\nstdin_file = 'tests/b.in'\ntest = { type = 'output', expected_file = 'tests/b.out' }")
+            .resolve(folder, Some(0))
+            .unwrap();
+        assert_eq!(checks[0].stdin.as_deref(), Some("2\n"));
+        assert_eq!(
+            checks[0].test,
+            CheckTest::Output {
+                expected: String::from("4\n")
+            }
+        );
+        assert_eq!(
+            checks[0].source,
+            ExpectedSource::File(folder.join("tests/b.out"))
+        );
+
+        let missing =
+            info("name = 'Double'\ntest = { type = 'output', expected_file = 'tests/c.out' }");
+        assert!(missing.resolve(folder, Some(0)).is_err());
+        let inline =
+            info("name = 'Double'\nstdin = '1 2'\ntest = { type = 'output', expected = '3' }");
+        let checks = inline.resolve(folder, None).unwrap();
+        assert_eq!(checks[0].stdin.as_deref(), Some("1 2"));
+        assert_eq!(checks[0].source, ExpectedSource::Inherited);
+    }
+
+    #[test]
+    fn cases_create_one_check_per_input() {
+        let (exo, _) = Exo::from_dir(&PathBuf::from(TEST_FILES_EXO)).unwrap();
+        let names: Vec<&str> = exo.checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(names, vec!["Double (a)", "Double (b)"]);
+        assert_eq!(exo.checks[1].stdin.as_deref(), Some("2\n"));
+        assert_eq!(
+            exo.checks[1].test,
+            CheckTest::Output {
+                expected: String::from("4\n")
+            }
+        );
+
+        let checks = info("name = 'Double'\nargs = ['-v']\ncases = 'tests/*.in'")
+            .resolve(Path::new(TEST_FILES_EXO), Some(0))
+            .unwrap();
+        assert_eq!(checks[0].args, vec!["-v"]);
+        assert!(info("name = 'None'\ncases = 'other/*.in'")
+            .resolve(Path::new(TEST_FILES_EXO), Some(0))
+            .is_err());
+    }
+
+    #[test]
+    fn table_rows_become_checks() {
+        let checks = info(
+            "name = 'Sum'\nargs = ['1', '1']\nstdin = 'in'\ncases = [\n  { name = 'zero', args = ['0', '0'], expected = '0' },\n  { stdin = 'other', expected = '2' },\n]",
+        )
+        .resolve(Path::new("."), Some(3))
+        .unwrap();
+        let names: Vec<&str> = checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(names, vec!["Sum (zero)", "Sum #2"]);
+        assert_eq!(checks[0].args, vec!["0", "0"]);
+        assert_eq!(checks[0].stdin.as_deref(), Some("in"));
+        assert_eq!(checks[1].args, vec!["1", "1"]);
+        assert_eq!(checks[1].stdin.as_deref(), Some("other"));
+        assert_eq!(
+            checks[1].test,
+            CheckTest::Output {
+                expected: String::from("2")
+            }
+        );
+        assert_eq!(checks[1].source, ExpectedSource::Case(3, 1));
+    }
+}
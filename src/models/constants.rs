@@ -2,6 +2,13 @@ pub const COURSE_INFO_FILE: &str = "course.toml";
 pub const COURSE_STATE_FILE: &str = ".course-state.toml";
 pub const SKILL_INFO_FILE: &str = "skill.toml";
 pub const EXO_INFO_FILE: &str = "exo.toml";
+pub const INSTRUCTION_FILE: &str = "instruction.md";
+pub const HINTS_FOLDER: &str = "hints";
 pub const EXO_STATE_FILE: &str = ".exo-state.toml";
+pub const ORIGINAL_FILES_FOLDER: &str = ".plx-original";
 pub const BUILD_FOLDER_NAME: &str = "build";
 pub const TARGET_FILE_BASE_NAME: &str = "exo";
+pub const BUILD_CACHE_FILE: &str = ".plx-build-cache.toml";
+pub const BUILD_SYSTEM_FOLDER_NAME: &str = "project";
+pub const USER_CONFIG_FOLDER: &str = "plx";
+pub const USER_CONFIG_FILE: &str = "config.toml";
@@ -1,6 +1,6 @@
 use crate::core::diff::diff::Diff;
 
-use super::key::Key;
+use super::{grade::ExoGrade, key::Key};
 
 /// Represents every possible app event
 #[derive(Debug, PartialEq, Eq)]
@@ -18,4 +18,5 @@ pub enum Event {
     RunEnd(usize),
     RunOutputLine(usize, String),
     RunFail(usize, String),
+    SubmissionGraded(usize, Vec<ExoGrade>),
 }
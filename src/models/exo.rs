@@ -1,15 +1,22 @@
+use std::time::Duration;
+
 use super::{
-    check::Check,
-    constants::{EXO_INFO_FILE, EXO_STATE_FILE},
+    check::{Check, CheckInfo, CheckTest, Comparison},
+    constants::{
+        EXO_INFO_FILE, EXO_STATE_FILE, HINTS_FOLDER, INSTRUCTION_FILE, ORIGINAL_FILES_FOLDER,
+    },
+    exo_defaults::ExoDefaults,
+    exo_metadata::ExoMetadata,
     exo_state::ExoState,
+    language::Language,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::core::{
-    compiler::compiler::Compiler,
+    compiler::compiler::{Compiler, CMAKE_FILE_NAME, MAKEFILE_NAMES},
     file_utils::{
         file_parser::{ParseError, ParseWarning},
-        file_utils::list_dir_files,
+        file_utils::{list_dir_files, read_file},
     },
     parser::{self, from_dir::FromDir},
 };
@@ -20,7 +27,17 @@ struct ExoInfo {
     name: String,
     instruction: Option<String>,
     #[serde(default)]
-    checks: Vec<Check>,
+    hints: Vec<String>,
+    /// Skill and exo folders to finish before this exo, relative to the course folder
+    #[serde(default)]
+    requires: Vec<std::path::PathBuf>,
+    /// Exo files provided to the student that shouldn't be edited, like a header
+    #[serde(default)]
+    readonly: Vec<std::path::PathBuf>,
+    #[serde(flatten)]
+    settings: ExoDefaults,
+    #[serde(flatten)]
+    metadata: ExoMetadata,
 }
 
 /// Contains the exo state info that can be found in .exo-state.toml
@@ -28,6 +45,9 @@ struct ExoInfo {
 pub(super) struct ExoStateInfo {
     pub(super) state: ExoState,
     pub(super) favorite: bool,
+    /// Number of hints revealed by the student
+    #[serde(default)]
+    pub(super) hints_used: usize,
 }
 
 /// Represents a Plx Exo
@@ -35,10 +55,30 @@ pub(super) struct ExoStateInfo {
 pub struct Exo {
     pub(crate) name: String,
     pub(crate) instruction: Option<String>,
+    pub(crate) language: Option<Language>,
     pub(crate) state: ExoState,
     pub(crate) files: Vec<std::path::PathBuf>,
+    /// Exo files that the student shouldn't edit, they are compiled like the other files
+    pub(crate) readonly: Vec<std::path::PathBuf>,
+    /// Read-only files that differ from their original copy, see `Exo::modified_readonly_files`
+    pub(crate) modified_readonly: Vec<std::path::PathBuf>,
+    /// Why the last reset of the exo failed, shown on the train page until the next save
+    pub(crate) reset_error: Option<String>,
     pub(crate) solutions: Vec<std::path::PathBuf>,
     pub(crate) checks: Vec<Check>,
+    /// Extra arguments given to the compiler
+    pub(crate) flags: Vec<String>,
+    /// Files outside of the exo folder compiled with the exo files, see `ExoDefaults::shared`
+    pub(crate) shared: Vec<std::path::PathBuf>,
+    /// Maximum duration of each check run, None to wait until the program ends
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) comparison: Comparison,
+    pub(crate) hints: Vec<String>,
+    pub(crate) hints_used: usize,
+    pub(crate) metadata: ExoMetadata,
+    pub(crate) requires: Vec<std::path::PathBuf>,
+    /// Why the exo can't be started yet, None if it's unlocked, see `Project::refresh_locks`
+    pub(crate) locked: Option<String>,
     pub(crate) favorite: bool,
     pub(crate) folder: std::path::PathBuf,
 }
@@ -48,6 +88,16 @@ impl FromDir for Exo {
     /// else Error
     fn from_dir(
         dir: &std::path::PathBuf,
+    ) -> Result<(Self, Vec<ParseWarning>), (ParseError, Vec<ParseWarning>)> {
+        Exo::from_dir_with_defaults(dir, &ExoDefaults::default())
+    }
+}
+impl Exo {
+    /// Same as `from_dir`, the settings missing in exo.toml are taken from `defaults`, the
+    /// settings of the skill and course containing the exo
+    pub fn from_dir_with_defaults(
+        dir: &std::path::PathBuf,
+        defaults: &ExoDefaults,
     ) -> Result<(Self, Vec<ParseWarning>), (ParseError, Vec<ParseWarning>)> {
         // Get the exo info and the state if it exists.
         let mut warnings = Vec::new();
@@ -55,6 +105,9 @@ impl FromDir for Exo {
         let exo_state_file = dir.join(EXO_STATE_FILE);
         let exo_info = parser::object_creator::create_object_from_file::<ExoInfo>(&exo_info_file)
             .map_err(|err| (err, vec![]))?;
+        // Checks of exo.toml come first, the inherited ones are added after them
+        let own_checks = exo_info.settings.checks.len();
+        let settings = exo_info.settings.relative_to(dir).inherit(defaults);
 
         // If the exo hasn't been started, the state file won't exist
         let exo_state =
@@ -77,23 +130,58 @@ impl FromDir for Exo {
         }
 
         Exo::check_exo_solutions(&exo_files, &solution_files, &mut warnings);
+        let readonly: Vec<std::path::PathBuf> = exo_info
+            .readonly
+            .iter()
+            .map(|file| dir.join(file))
+            .collect();
+        for file in readonly.iter().filter(|file| !exo_files.contains(file)) {
+            warnings.push(ParseWarning::ExoFileNotFound(format!(
+                "Read-only file {:?} is not an exo file",
+                file
+            )));
+        }
+        for file in settings.shared.iter().filter(|file| !file.is_file()) {
+            warnings.push(ParseWarning::ExoFileNotFound(format!(
+                "Shared file {:?} of {:?} doesn't exist",
+                file, dir
+            )));
+        }
+        let checks = Exo::resolve_checks(dir, &settings.checks, own_checks)
+            .map_err(|err| (err, std::mem::take(&mut warnings)))?;
+        Exo::check_checks(&exo_info_file, &checks, &mut warnings);
+        let language = Exo::find_language(dir, settings.language, &exo_files, &mut warnings);
+        let instruction = Exo::read_instruction(dir, exo_info.instruction, &mut warnings)
+            .map_err(|err| (err, std::mem::take(&mut warnings)))?;
+        let hints = Exo::read_hints(dir, exo_info.hints, &mut warnings)
+            .map_err(|err| (err, std::mem::take(&mut warnings)))?;
 
-        Ok((
-            Self {
-                name: exo_info.name,
-                instruction: exo_info.instruction,
-                checks: exo_info.checks,
-                state: exo_state.state,
-                files: exo_files,
-                favorite: exo_state.favorite,
-                solutions: solution_files,
-                folder: dir.to_path_buf(),
-            },
-            warnings,
-        ))
+        let mut exo = Self {
+            name: exo_info.name,
+            instruction,
+            language,
+            checks,
+            flags: settings.flags.unwrap_or_default(),
+            shared: settings.shared,
+            timeout: settings.timeout.map(Duration::from_secs),
+            comparison: settings.comparison.unwrap_or_default(),
+            hints_used: exo_state.hints_used.min(hints.len()),
+            hints,
+            metadata: exo_info.metadata,
+            requires: exo_info.requires,
+            locked: None,
+            state: exo_state.state,
+            files: exo_files,
+            readonly,
+            modified_readonly: vec![],
+            reset_error: None,
+            favorite: exo_state.favorite,
+            solutions: solution_files,
+            folder: dir.to_path_buf(),
+        };
+        exo.modified_readonly = exo.modified_readonly_files();
+        Ok((exo, warnings))
     }
-}
-impl Exo {
     /// Finds exo and solution from a bunch of folder files
     fn find_exo_and_solution_files(
         files: Vec<std::path::PathBuf>,
@@ -108,20 +196,136 @@ impl Exo {
                 .unwrap_or_default();
 
             // Ignore our files
-            if file_extension == "toml" {
+            if file_extension == "toml"
+                || file_path
+                    .file_name()
+                    .is_some_and(|name| name == INSTRUCTION_FILE)
+            {
                 continue;
             }
             if file_path_str.contains(".sol.") {
                 solution_files.push(file_path);
                 continue;
             }
-            // TODO maybe make sure we don't mix .c with .java files here ?
-            // We need to be careful adding this because .c can be mixed with .cpp, .h,
-            // .hpp etc...
+            // Mixing languages is checked later, see `find_language`
             exo_files.push(file_path);
         }
         (exo_files, solution_files)
     }
+    /// Gets the exo instruction, the markdown of instruction.md takes precedence over the
+    /// instruction of exo.toml
+    fn read_instruction(
+        dir: &std::path::PathBuf,
+        explicit: Option<String>,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Option<String>, ParseError> {
+        let instruction_file = dir.join(INSTRUCTION_FILE);
+        if !instruction_file.is_file() {
+            return Ok(explicit);
+        }
+        if explicit.is_some() {
+            warnings.push(ParseWarning::DuplicateInstruction(format!(
+                "The instruction of {:?} is defined in {} and {}, {} is used",
+                dir, EXO_INFO_FILE, INSTRUCTION_FILE, INSTRUCTION_FILE
+            )));
+        }
+        read_file(&instruction_file)
+            .map(Some)
+            .map_err(|err| ParseError::ReadFileError(format!("{:?}: {}", instruction_file, err)))
+    }
+    /// Gets the exo hints in the order they are revealed, the markdown files of the hints folder
+    /// sorted by name take precedence over the hints of exo.toml
+    fn read_hints(
+        dir: &std::path::PathBuf,
+        explicit: Vec<String>,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Result<Vec<String>, ParseError> {
+        let hints_folder = dir.join(HINTS_FOLDER);
+        if !hints_folder.is_dir() {
+            return Ok(explicit);
+        }
+        let mut hint_files: Vec<std::path::PathBuf> = list_dir_files(&hints_folder)
+            .map_err(|err| ParseError::FileDiscoveryFailed(err.to_string()))?
+            .into_iter()
+            .filter(|file| file.extension().is_some_and(|extension| extension == "md"))
+            .collect();
+        if hint_files.is_empty() {
+            return Ok(explicit);
+        }
+        if !explicit.is_empty() {
+            warnings.push(ParseWarning::DuplicateHints(format!(
+                "The hints of {:?} are defined in {} and {}, {} is used",
+                dir, EXO_INFO_FILE, HINTS_FOLDER, HINTS_FOLDER
+            )));
+        }
+        hint_files.sort();
+        hint_files
+            .iter()
+            .map(|file| {
+                read_file(file)
+                    .map_err(|err| ParseError::ReadFileError(format!("{:?}: {}", file, err)))
+            })
+            .collect()
+    }
+    /// Gets the exo language, either set explicitly in exo.toml or detected from the exo files
+    /// Warns about files written in a language that can't be mixed with the exo language
+    fn find_language(
+        dir: &std::path::PathBuf,
+        explicit: Option<Language>,
+        exo_files: &[std::path::PathBuf],
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Option<Language> {
+        let (language, incompatible_files) = match explicit {
+            Some(language) => (Some(language), language.incompatible_files(exo_files)),
+            None => Language::detect(exo_files),
+        };
+        if let Some(language) = language {
+            if !incompatible_files.is_empty() {
+                warnings.push(ParseWarning::IncompatibleLanguages(format!(
+                    "Files {:?} can't be mixed with the {:?} language of exo {:?}",
+                    incompatible_files, language, dir
+                )));
+            }
+        }
+        language
+    }
+    /// Builds the checks of the exo, reading their input and expected output files
+    fn resolve_checks(
+        dir: &std::path::Path,
+        infos: &[CheckInfo],
+        own_checks: usize,
+    ) -> Result<Vec<Check>, ParseError> {
+        let mut checks = Vec::new();
+        for (index, info) in infos.iter().enumerate() {
+            let index = Some(index).filter(|index| *index < own_checks);
+            checks.append(&mut info.resolve(dir, index)?);
+        }
+        Ok(checks)
+    }
+    /// Warns about checks that share the same name or have an empty expected output
+    fn check_checks(
+        exo_info_file: &std::path::PathBuf,
+        checks: &[Check],
+        warnings: &mut Vec<ParseWarning>,
+    ) {
+        for (i, check) in checks.iter().enumerate() {
+            if checks[..i].iter().any(|other| other.name == check.name) {
+                warnings.push(ParseWarning::DuplicateCheckName(format!(
+                    "Check {:?} is defined multiple times in {:?}",
+                    check.name, exo_info_file
+                )));
+            }
+            match &check.test {
+                CheckTest::Output { expected } if expected.trim().is_empty() => {
+                    warnings.push(ParseWarning::EmptyExpectedOutput(format!(
+                        "Check {:?} in {:?} expects an empty output",
+                        check.name, exo_info_file
+                    )))
+                }
+                CheckTest::Output { .. } => {}
+            }
+        }
+    }
     // Check every solution file and check that it corresponds to an actual exo file
     fn check_exo_solutions(
         exo_files: &Vec<std::path::PathBuf>,
@@ -173,47 +377,139 @@ impl Exo {
         }
     }
 
-    /// Tries to find a `main` file or returns the first file in the list of exo files
+    /// Tries to find a `main` file or returns the first file in the list of editable exo files
     pub fn get_main_file(&self) -> Option<&std::path::PathBuf> {
-        match self.files.iter().find(|file| {
+        let editable = self.editable_files();
+        match editable.iter().find(|file| {
             if let Some(file_name) = file.file_stem() {
                 return file_name == "main";
             }
             return false;
         }) {
-            Some(file) => Some(file),
-            None => self.files.first(),
+            Some(file) => Some(*file),
+            None => editable.first().copied(),
         }
     }
 
-    /// Computes the required compiler based on the file extension
+    /// Returns the exo files that the student can edit, every file that isn't read-only
+    pub fn editable_files(&self) -> Vec<&std::path::PathBuf> {
+        self.files
+            .iter()
+            .filter(|file| !self.readonly.contains(file))
+            .collect()
+    }
+
+    /// Returns the read-only files that differ from their original version
+    /// Outside of git, nothing can be detected before the first start, see `Exo::original_file`
+    pub fn modified_readonly_files(&self) -> Vec<std::path::PathBuf> {
+        self.readonly
+            .iter()
+            .filter(|file| {
+                self.original_file(file)
+                    .is_some_and(|original| std::fs::read(file).ok() != Some(original))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the original content of `file`: its last committed version when the course is
+    /// a git repository, so the files edited before the exo was opened are detected too, or the
+    /// copy saved by `Exo::save_original_files` otherwise
+    fn original_file(&self, file: &std::path::Path) -> Option<Vec<u8>> {
+        let name = file.file_name()?;
+        let committed = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&self.folder)
+            .arg("show")
+            .arg(format!("HEAD:./{}", name.to_string_lossy()))
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| output.stdout);
+        committed.or_else(|| std::fs::read(self.folder.join(ORIGINAL_FILES_FOLDER).join(name)).ok())
+    }
+
+    /// Computes the required compiler based on the exo language
+    /// Exos containing a `CMakeLists.txt` or a `Makefile` are built using the matching build
+    /// system instead
     pub fn compiler(&self) -> Option<Compiler> {
-        let mut compiler = None;
+        if self.has_file_named(&[CMAKE_FILE_NAME]) {
+            return Some(Compiler::CMake);
+        }
+        if self.has_file_named(&MAKEFILE_NAMES) {
+            return Some(Compiler::Make);
+        }
+        self.language?.compiler()
+    }
+
+    /// Saves a copy of the exo files, so the exo can be reset to its starting state later
+    /// Nothing is done if a copy already exists, the first one is the original
+    /// The copy ignores itself, so it is never committed with the course
+    pub fn save_original_files(&self) -> std::io::Result<()> {
+        let folder = self.folder.join(ORIGINAL_FILES_FOLDER);
+        if folder.exists() {
+            return Ok(());
+        }
+        std::fs::create_dir(&folder)?;
+        std::fs::write(folder.join(".gitignore"), "*\n")?;
         for file in &self.files {
-            let extension = file
-                .extension()
-                .unwrap_or_default()
-                .to_str()
-                .unwrap_or_default();
-            if extension == "cpp" || extension == "cc" {
-                compiler = Some(Compiler::Gxx);
-                break;
-            } else if extension == "c" {
-                compiler = Some(Compiler::Gcc);
+            std::fs::copy(file, folder.join(file.file_name().unwrap_or_default()))?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the exo files with their original version, see `Exo::original_file`
+    /// Returns false if no original was found for any file
+    pub fn restore_original_files(&self) -> std::io::Result<bool> {
+        let mut restored = false;
+        for file in &self.files {
+            if let Some(original) = self.original_file(file) {
+                std::fs::write(file, original)?;
+                restored = true;
             }
         }
-        compiler
+        Ok(restored)
+    }
+
+    /// Checks if one of the exo files has one of the given names
+    fn has_file_named(&self, names: &[&str]) -> bool {
+        self.files.iter().any(|file| {
+            file.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| names.contains(&name))
+        })
     }
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use std::str::FromStr;
 
-    use crate::models::check::CheckTest;
+    use crate::models::check::{CheckTest, ExpectedSource};
 
     use super::*;
 
+    /// Copies the example exo `folder` in a temporary folder, for the tests changing its files
+    pub(crate) fn copy_example_exo(folder: &str) -> (tempfile::TempDir, Exo) {
+        fn copy_folder(from: &std::path::Path, to: &std::path::Path) {
+            std::fs::create_dir_all(to).unwrap();
+            for entry in std::fs::read_dir(from).unwrap() {
+                let path = entry.unwrap().path();
+                let target = to.join(path.file_name().unwrap());
+                if path.is_dir() {
+                    copy_folder(&path, &target);
+                } else {
+                    std::fs::copy(&path, &target).unwrap();
+                }
+            }
+        }
+        let dir = tempfile::tempdir().unwrap();
+        copy_folder(std::path::Path::new(folder), dir.path());
+        let (exo, _) = Exo::from_dir(&dir.path().to_path_buf()).unwrap();
+        (dir, exo)
+    }
+
     #[test]
     fn test_parse_full_intro_basic_args() {
         let file_path = "examples/mock-plx-project/intro/basic-args";
@@ -224,6 +520,7 @@ mod test {
                 String::from(
                     "The 2 first program arguments are the firstname and number of legs of a dog. Print a full sentence about the dog. Make sure there is at least 2 arguments, print an error if not.")
             ),
+        language: Some(Language::C),
         state: ExoState::Todo,
         folder: file_path.into(),
         files: vec![
@@ -243,11 +540,15 @@ mod test {
                     String::from("5"),
                 ],
                 test: CheckTest::Output {expected : String::from("The dog is Joe and has 5 legs")},
+                stdin: None,
+                source: ExpectedSource::Check(0),
             },
             Check {
                 name: String::from("No arg -> error"),
                 args: vec![],
                 test: CheckTest::Output {expected: String::from("Error: missing argument firstname and legs number")},
+                stdin: None,
+                source: ExpectedSource::Check(1),
             },
             Check {
                 name: String::from("One arg -> error"),
@@ -255,9 +556,23 @@ mod test {
                     String::from("Joe"),
                 ],
                 test: CheckTest::Output {expected: String::from("Error: missing argument firstname and legs number")},
+                stdin: None,
+                source: ExpectedSource::Check(2),
             },
         ],
         favorite: false,
+        hints: vec![],
+        flags: vec![],
+        shared: vec![],
+        timeout: None,
+        comparison: Comparison::Exact,
+        hints_used: 0,
+        metadata: ExoMetadata::default(),
+        requires: vec![],
+        locked: None,
+        readonly: vec![],
+        modified_readonly: vec![],
+        reset_error: None,
     };
         assert_eq!(
             expected,
@@ -273,12 +588,25 @@ mod test {
         let expected = Exo {
             name: String::from("Exo Done"),
             instruction: None,
+            language: Some(Language::C),
             folder: file_path.into(),
             checks: vec![],
             files: vec![std::path::PathBuf::from_str(file_path)
                 .unwrap()
                 .join("main.c")],
             favorite: false,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            requires: vec![],
+            locked: None,
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
             state: ExoState::Done,
             solutions: vec![],
         };
@@ -292,12 +620,25 @@ mod test {
         let expected = Exo {
             name: String::from("Favorite Exercise"),
             instruction: None,
+            language: Some(Language::C),
             checks: vec![],
             folder: file_path.into(),
             files: vec![std::path::PathBuf::from_str(file_path)
                 .unwrap()
                 .join("main.c")],
             favorite: true,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            requires: vec![],
+            locked: None,
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
             state: ExoState::Todo,
             solutions: vec![],
         };
@@ -312,12 +653,25 @@ mod test {
         let expected = Exo {
             name: String::from("In Progress"),
             instruction: None,
+            language: Some(Language::C),
             checks: vec![],
             folder: file_path.into(),
             files: vec![std::path::PathBuf::from_str(file_path)
                 .unwrap()
                 .join("main.c")],
             favorite: false,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            requires: vec![],
+            locked: None,
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
             state: ExoState::InProgress,
             solutions: vec![],
         };
@@ -340,12 +694,25 @@ mod test {
         let expected = Exo {
             name: String::from("Multiple Sols"),
             instruction: None,
+            language: Some(Language::C),
             folder: file_path.into(),
             checks: vec![],
             files: vec![std::path::PathBuf::from_str(file_path)
                 .unwrap()
                 .join("main.c")],
             favorite: false,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            requires: vec![],
+            locked: None,
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
             state: ExoState::Todo,
             solutions: sol_files.clone(),
         };
@@ -354,6 +721,16 @@ mod test {
         assert!(matches!(warnings[0], ParseWarning::ExoFileNotFound(_)));
     }
     #[test]
+    fn test_java_exo_language() {
+        let file_path = "examples/full/pointers/crash-debug-java";
+        let (exo, warnings) = Exo::from_dir(&(file_path.into())).unwrap();
+        assert_eq!(exo.language, Some(Language::Java));
+        assert!(exo.compiler().is_none());
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseWarning::IncompatibleLanguages(_))));
+    }
+    #[test]
     fn test_no_exo_info() {
         let file_path = "examples/mock-plx-project/mock-skill/no-exo-info";
         let ret = Exo::from_dir(&(file_path.into()));
@@ -385,11 +762,24 @@ mod test {
             name: String::from("No Sol"),
             folder: file_path.into(),
             instruction: None,
+            language: Some(Language::C),
             checks: vec![],
             files: vec![std::path::PathBuf::from_str(file_path)
                 .unwrap()
                 .join("main.c")],
             favorite: false,
+            hints: vec![],
+            flags: vec![],
+            shared: vec![],
+            timeout: None,
+            comparison: Comparison::Exact,
+            hints_used: 0,
+            metadata: ExoMetadata::default(),
+            requires: vec![],
+            locked: None,
+            readonly: vec![],
+            modified_readonly: vec![],
+            reset_error: None,
             state: ExoState::Todo,
             solutions: vec![],
         };
@@ -397,4 +787,115 @@ mod test {
         assert_eq!(warnings.len(), 1);
         assert!(matches!(warnings[0], ParseWarning::NoSolutionFile(_)));
     }
+
+    #[test]
+    fn test_bad_checks_are_reported() {
+        let file_path = "examples/mock-plx-project/mock-skill/bad-checks";
+        let (exo, warnings) = Exo::from_dir(&(file_path.into())).unwrap();
+        assert_eq!(exo.checks.len(), 3);
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(warnings[0], ParseWarning::DuplicateCheckName(_)));
+        assert!(matches!(warnings[1], ParseWarning::EmptyExpectedOutput(_)));
+        assert!(warnings[0].to_string().contains("Say hello"));
+    }
+
+    #[test]
+    fn original_files_are_restored() {
+        let (dir, exo) = copy_example_exo("examples/mock-plx-project/intro/basic-args");
+        let original = std::fs::read_to_string(dir.path().join("main.c")).unwrap();
+        assert!(!exo.restore_original_files().unwrap());
+
+        exo.save_original_files().unwrap();
+        std::fs::write(dir.path().join("main.c"), "mangled").unwrap();
+        // The first copy is kept
+        exo.save_original_files().unwrap();
+        assert!(exo.restore_original_files().unwrap());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("main.c")).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn committed_files_are_the_original() {
+        let (dir, exo) = copy_example_exo("examples/mock-plx-project/intro/basic-args");
+        let original = std::fs::read_to_string(dir.path().join("main.c")).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=plx", "-c", "user.email=plx@example.com"])
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-qm", "Exo"]);
+
+        // The file was edited before the exo was opened the first time
+        std::fs::write(dir.path().join("main.c"), "mangled").unwrap();
+        exo.save_original_files().unwrap();
+        assert!(exo.restore_original_files().unwrap());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("main.c")).unwrap(),
+            original
+        );
+        // The saved copy isn't part of the course
+        git(&["add", "."]);
+        git(&["diff", "--cached", "--quiet"]);
+    }
+
+    #[test]
+    fn instruction_file_takes_precedence() {
+        let file_path = "examples/mock-plx-project/mock-skill/instruction-file";
+        let path = std::path::PathBuf::from_str(file_path).unwrap();
+        let (exo, warnings) = Exo::from_dir(&path).unwrap();
+        assert_eq!(
+            exo.instruction.as_deref(),
+            Some("# Title\n\nPrint `hello`\n")
+        );
+        assert_eq!(exo.files, vec![path.join("main.c")]);
+        assert!(warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseWarning::DuplicateInstruction(_))));
+    }
+
+    #[test]
+    fn hints_are_read_in_order() {
+        let file_path = "examples/mock-plx-project/mock-skill/hints-list";
+        let (exo, _) = Exo::from_dir(&std::path::PathBuf::from_str(file_path).unwrap()).unwrap();
+        assert_eq!(exo.hints, vec!["Use printf", "Add a new line"]);
+        assert_eq!(exo.hints_used, 2);
+
+        let file_path = "examples/mock-plx-project/mock-skill/hints-folder";
+        let (exo, warnings) =
+            Exo::from_dir(&std::path::PathBuf::from_str(file_path).unwrap()).unwrap();
+        assert_eq!(exo.hints, vec!["Use **printf**\n", "Use `%d`\n"]);
+        assert!(warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseWarning::DuplicateHints(_))));
+    }
+
+    #[test]
+    fn readonly_files_are_not_editable() {
+        let file_path = "examples/mock-plx-project/mock-skill/readonly-files";
+        let path = std::path::PathBuf::from_str(file_path).unwrap();
+        let (exo, warnings) = Exo::from_dir(&path).unwrap();
+        assert_eq!(exo.editable_files(), vec![&path.join("queue.c")]);
+        assert_eq!(exo.get_main_file(), Some(&path.join("queue.c")));
+        assert!(warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseWarning::ExoFileNotFound(_))));
+
+        let (dir, exo) = copy_example_exo(file_path);
+        assert!(exo.modified_readonly_files().is_empty());
+        exo.save_original_files().unwrap();
+        std::fs::write(dir.path().join("queue.c"), "// edited").unwrap();
+        assert!(exo.modified_readonly_files().is_empty());
+        std::fs::write(dir.path().join("main.c"), "// edited").unwrap();
+        let (exo, _) = Exo::from_dir(&dir.path().to_path_buf()).unwrap();
+        assert_eq!(exo.modified_readonly, vec![dir.path().join("main.c")]);
+    }
 }
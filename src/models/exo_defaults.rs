@@ -0,0 +1,161 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    check::{CheckInfo, Comparison},
+    language::Language,
+};
+
+/// Exo settings that can be set in course.toml and skill.toml to be shared by their exos
+/// Each level overrides its parent: exo.toml over skill.toml over course.toml
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+pub struct ExoDefaults {
+    pub language: Option<Language>,
+    /// Extra arguments given to the compiler, like `-Wall` or `-lm`
+    pub flags: Option<Vec<String>>,
+    /// Maximum duration of each check run, in seconds
+    pub timeout: Option<u64>,
+    /// How the output of the checks is compared to the expected output
+    pub comparison: Option<Comparison>,
+    /// Files compiled with the exo files without being part of them, like a `list.c` used by every
+    /// exo of a skill. Paths are relative to the folder of the toml file
+    #[serde(default)]
+    pub shared: Vec<PathBuf>,
+    /// Checks of the exo, at course and skill levels they are templates added to every exo that
+    /// doesn't have a check with the same name
+    #[serde(default)]
+    pub checks: Vec<CheckInfo>,
+}
+
+impl ExoDefaults {
+    /// Makes the paths of the settings start from `dir`, the folder of the toml file, so they
+    /// stay valid when the settings are inherited by exos in other folders
+    pub fn relative_to(mut self, dir: &Path) -> Self {
+        self.shared = self
+            .shared
+            .iter()
+            .map(|file| normalize(&dir.join(file)))
+            .collect();
+        self
+    }
+
+    /// Completes the values missing in `self` with the values of `parent`
+    pub fn inherit(mut self, parent: &ExoDefaults) -> Self {
+        self.language = self.language.or(parent.language);
+        self.flags = self.flags.or_else(|| parent.flags.clone());
+        self.timeout = self.timeout.or(parent.timeout);
+        self.comparison = self.comparison.or(parent.comparison);
+        for file in &parent.shared {
+            if !self.shared.contains(file) {
+                self.shared.push(file.clone());
+            }
+        }
+        for check in &parent.checks {
+            if !self.checks.iter().any(|own| own.name == check.name) {
+                self.checks.push(check.clone());
+            }
+        }
+        self
+    }
+}
+
+/// Removes the `.` and `..` of `path` without accessing the file system, so the same file
+/// shared by different folders has the same path
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{core::parser::toml_parser::toml_deserialize, models::check::CheckTestInfo};
+
+    use super::*;
+
+    fn check(name: &str, expected: &str) -> CheckInfo {
+        CheckInfo {
+            name: String::from(name),
+            args: vec![],
+            stdin: None,
+            stdin_file: None,
+            cases: None,
+            test: Some(CheckTestInfo::Output {
+                expected: Some(String::from(expected)),
+                expected_file: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn exo_values_override_parent_values() {
+        let course: ExoDefaults = toml_deserialize(
+            "flags = ['-Wall']\ntimeout = 2\ncomparison = 'ignore-case'\nlanguage = 'c'",
+        )
+        .unwrap();
+        let exo = ExoDefaults {
+            flags: Some(vec![]),
+            timeout: Some(5),
+            ..Default::default()
+        };
+        let merged = exo.inherit(&course);
+        assert_eq!(merged.flags, Some(vec![]));
+        assert_eq!(merged.timeout, Some(5));
+        assert_eq!(merged.comparison, Some(Comparison::IgnoreCase));
+        assert_eq!(merged.language, Some(Language::C));
+    }
+
+    #[test]
+    fn shared_files_are_relative_to_their_toml() {
+        let skill = ExoDefaults {
+            shared: vec![PathBuf::from("common/list.c")],
+            ..Default::default()
+        }
+        .relative_to(Path::new("data"));
+        let exo = ExoDefaults {
+            shared: vec![
+                PathBuf::from("../common/list.c"),
+                PathBuf::from("./stack.h"),
+            ],
+            ..Default::default()
+        }
+        .relative_to(Path::new("data/stack"));
+        assert_eq!(
+            exo.inherit(&skill).shared,
+            vec![
+                PathBuf::from("data/common/list.c"),
+                PathBuf::from("data/stack/stack.h"),
+            ]
+        );
+    }
+
+    #[test]
+    fn template_checks_are_added_unless_overridden() {
+        let skill = ExoDefaults {
+            checks: vec![check("No args", "usage"), check("Help", "help")],
+            ..Default::default()
+        };
+        let exo = ExoDefaults {
+            checks: vec![check("Help", "custom help")],
+            ..Default::default()
+        };
+        assert_eq!(
+            exo.inherit(&skill).checks,
+            vec![check("Help", "custom help"), check("No args", "usage")]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How hard an exo is, to help students pick exos at their level
+#[derive(Serialize, Deserialize, ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let difficulty = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{}", difficulty)
+    }
+}
+
+/// Optional information about an exo found in exo.toml, it doesn't change how the exo is checked
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+pub struct ExoMetadata {
+    pub difficulty: Option<Difficulty>,
+    /// Topics of the exo, like `pointers` or `strings`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Time needed by a student to do the exo
+    pub estimated_minutes: Option<u32>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// Where the exo comes from, like a book, an exam or an url
+    pub source: Option<String>,
+}
+
+/// Selects exos by their metadata, an empty filter selects every exo
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ExoFilter {
+    /// Tags the exo must all have
+    pub tags: Vec<String>,
+    pub difficulty: Option<Difficulty>,
+}
+
+impl ExoFilter {
+    /// Returns true if the filter doesn't exclude any exo
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.difficulty.is_none()
+    }
+
+    /// Returns true if an exo with this `metadata` is selected, tags are compared ignoring case
+    pub fn matches(&self, metadata: &ExoMetadata) -> bool {
+        let has_tags = self.tags.iter().all(|tag| {
+            metadata
+                .tags
+                .iter()
+                .any(|exo_tag| exo_tag.eq_ignore_ascii_case(tag))
+        });
+        let has_difficulty = self
+            .difficulty
+            .is_none_or(|difficulty| metadata.difficulty == Some(difficulty));
+        has_tags && has_difficulty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_is_optional() {
+        let metadata: ExoMetadata = toml::from_str("").unwrap();
+        assert_eq!(metadata, ExoMetadata::default());
+        let metadata: ExoMetadata = toml::from_str(
+            "difficulty = 'medium'\ntags = ['pointers']\nestimated_minutes = 15\nauthors = ['Sam']",
+        )
+        .unwrap();
+        assert_eq!(metadata.difficulty, Some(Difficulty::Medium));
+        assert_eq!(metadata.estimated_minutes, Some(15));
+        assert_eq!(metadata.source, None);
+    }
+
+    #[test]
+    fn filter_needs_every_tag_and_the_difficulty() {
+        let metadata = ExoMetadata {
+            difficulty: Some(Difficulty::Easy),
+            tags: vec![String::from("Pointers"), String::from("strings")],
+            ..Default::default()
+        };
+        assert!(ExoFilter::default().matches(&metadata));
+        let filter = ExoFilter {
+            tags: vec![String::from("pointers"), String::from("strings")],
+            difficulty: Some(Difficulty::Easy),
+        };
+        assert!(filter.matches(&metadata));
+        let filter = ExoFilter {
+            tags: vec![String::from("pointers"), String::from("files")],
+            difficulty: None,
+        };
+        assert!(!filter.matches(&metadata));
+        let filter = ExoFilter {
+            tags: vec![],
+            difficulty: Some(Difficulty::Hard),
+        };
+        assert!(!filter.matches(&metadata));
+    }
+}
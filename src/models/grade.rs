@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+/// Outcome of the grading of one exo of a submission
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum GradeStatus {
+    /// The exo compiled and its checks were run
+    Graded,
+    /// The submission doesn't contain this exo
+    Missing,
+    /// The submitted files couldn't be compiled
+    CompilationFailed,
+}
+
+/// Grade of one exo of a submission
+#[derive(Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct ExoGrade {
+    /// Folder of the exo relative to the course root, also used in the submissions
+    pub(crate) folder: PathBuf,
+    pub(crate) status: GradeStatus,
+    /// Number of checks that passed
+    pub(crate) passed: usize,
+    pub(crate) total: usize,
+}
+
+impl ExoGrade {
+    /// Returns the fraction of checks that passed, between 0 and 1
+    pub fn score(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.passed as f64 / self.total as f64
+    }
+}
@@ -10,6 +10,8 @@ pub enum Key {
     L,
     N,
     P,
+    X,
+    I,
     // E,
     Enter,
     Esc,
@@ -28,6 +30,8 @@ impl Key {
             Key::L => "Move right",
             Key::N => "Next block",
             Key::P => "Previous block",
+            Key::X => "Reset the exo files to their original version",
+            Key::I => "Show the next hint of the exo",
             // Key::E => "Edit exo",
             Key::Enter => "Enter to continue",
             Key::Esc => "Go back",
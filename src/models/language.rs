@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::compiler::compiler::Compiler;
+
+/// Represents the programming language of an exo
+/// It can be set explicitly with `language = "c"` in exo.toml, otherwise it is detected using the
+/// extensions of the exo files
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    C,
+    #[serde(alias = "c++")]
+    Cpp,
+    Java,
+}
+
+/// Describes everything PLX needs to know about a language
+struct LanguageInfo {
+    language: Language,
+    /// Extensions of the source files written in this language, they are compiled and define the
+    /// language of the exo
+    source_extensions: &'static [&'static str],
+    /// Extensions of the header files written in this language, they are only included by sources
+    header_extensions: &'static [&'static str],
+    /// Other languages whose source files can be part of an exo in this language
+    accepts: &'static [Language],
+    /// Extension used to find the syntax highlighting definition
+    syntax_extension: &'static str,
+    /// Compiler used to build exos in this language, None if we can't build them yet
+    compiler: Option<Compiler>,
+}
+
+/// Every supported language
+/// Files with an extension not listed here (Makefile, txt...) are not bound to a language and can be
+/// found in any exo
+static LANGUAGES: [LanguageInfo; 3] = [
+    LanguageInfo {
+        language: Language::C,
+        source_extensions: &["c"],
+        header_extensions: &["h"],
+        accepts: &[],
+        syntax_extension: "c",
+        compiler: Some(Compiler::Gcc),
+    },
+    LanguageInfo {
+        language: Language::Cpp,
+        source_extensions: &["cpp", "cc", "cxx"],
+        header_extensions: &["hpp", "hh", "hxx"],
+        accepts: &[Language::C],
+        syntax_extension: "cpp",
+        compiler: Some(Compiler::Gxx),
+    },
+    LanguageInfo {
+        language: Language::Java,
+        source_extensions: &["java"],
+        header_extensions: &[],
+        accepts: &[],
+        syntax_extension: "java",
+        compiler: None,
+    },
+];
+
+impl Language {
+    fn info(&self) -> &'static LanguageInfo {
+        // Every language has an entry in LANGUAGES
+        LANGUAGES
+            .iter()
+            .find(|info| info.language == *self)
+            .unwrap_or(&LANGUAGES[0])
+    }
+
+    /// Finds the language of a source file using its extension
+    pub fn from_path(path: &std::path::Path) -> Option<Language> {
+        let extension = path.extension()?.to_str()?;
+        LANGUAGES
+            .iter()
+            .find(|info| info.source_extensions.contains(&extension))
+            .map(|info| info.language)
+    }
+
+    /// Finds the language of a header file using its extension
+    fn from_header_path(path: &std::path::Path) -> Option<Language> {
+        let extension = path.extension()?.to_str()?;
+        LANGUAGES
+            .iter()
+            .find(|info| info.header_extensions.contains(&extension))
+            .map(|info| info.language)
+    }
+
+    /// Finds the language built by `compiler`
+    pub fn built_with(compiler: &Compiler) -> Option<Language> {
+        LANGUAGES
+            .iter()
+            .find(|info| info.compiler.as_ref() == Some(compiler))
+            .map(|info| info.language)
+    }
+
+    /// Returns true if files written in `other` can be part of an exo in this language
+    pub fn accepts(&self, other: Language) -> bool {
+        *self == other || self.info().accepts.contains(&other)
+    }
+
+    /// Detects the language of an exo from its source files, headers don't change the language
+    /// The detected language is the one accepting the languages of every other source file
+    /// Returns the detected language and the files that don't fit in it
+    pub fn detect(files: &[std::path::PathBuf]) -> (Option<Language>, Vec<&std::path::PathBuf>) {
+        let file_languages: Vec<(&std::path::PathBuf, Language)> = files
+            .iter()
+            .filter_map(|file| Some((file, Language::from_path(file)?)))
+            .collect();
+        let detected = file_languages
+            .iter()
+            .map(|(_, language)| *language)
+            .find(|candidate| {
+                file_languages
+                    .iter()
+                    .all(|(_, language)| candidate.accepts(*language))
+            })
+            // No language accepts every file, take the first one and report the others
+            .or(file_languages.first().map(|(_, language)| *language));
+        let incompatible = match detected {
+            Some(detected) => detected.incompatible_files(files),
+            None => vec![],
+        };
+        (detected, incompatible)
+    }
+
+    /// Returns the files that are written in a language this language doesn't accept
+    /// Headers can also be included in the languages accepting theirs, like C++ headers in C
+    pub fn incompatible_files<'a>(
+        &self,
+        files: &'a [std::path::PathBuf],
+    ) -> Vec<&'a std::path::PathBuf> {
+        files
+            .iter()
+            .filter(|file| {
+                Language::from_path(file).is_some_and(|other| !self.accepts(other))
+                    || Language::from_header_path(file)
+                        .is_some_and(|other| !self.accepts(other) && !other.accepts(*self))
+            })
+            .collect()
+    }
+
+    /// Extensions of the source files compiled in an exo of this language, including the sources
+    /// of the languages it accepts
+    pub fn source_extensions(&self) -> Vec<&'static str> {
+        let mut extensions = self.info().source_extensions.to_vec();
+        for other in self.info().accepts {
+            extensions.extend_from_slice(other.info().source_extensions);
+        }
+        extensions
+    }
+
+    /// Extension used by syntect to find the syntax of this language
+    pub fn syntax_extension(&self) -> &'static str {
+        self.info().syntax_extension
+    }
+
+    /// Compiler needed to build exos in this language
+    pub fn compiler(&self) -> Option<Compiler> {
+        self.info().compiler.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn paths(files: &[&str]) -> Vec<PathBuf> {
+        files.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn detect_c_exo() {
+        let files = paths(&["main.c", "queue.c", "queue.h"]);
+        assert_eq!(Language::detect(&files), (Some(Language::C), vec![]));
+    }
+
+    #[test]
+    fn cpp_accepts_c_files() {
+        let files = paths(&["main.c", "exo_parser.cpp", "exo.h"]);
+        assert_eq!(Language::detect(&files), (Some(Language::Cpp), vec![]));
+    }
+
+    #[test]
+    fn java_and_c_are_incompatible() {
+        let files = paths(&["Main.java", "main.c"]);
+        let (language, incompatible) = Language::detect(&files);
+        assert_eq!(language, Some(Language::Java));
+        assert_eq!(incompatible, vec![&files[1]]);
+    }
+
+    #[test]
+    fn no_language_without_source_files() {
+        let files = paths(&["Makefile", "queue.h"]);
+        assert_eq!(Language::detect(&files), (None, vec![]));
+    }
+
+    #[test]
+    fn headers_dont_change_the_language() {
+        let files = paths(&["main.c", "list.hpp", "list.h"]);
+        assert_eq!(Language::detect(&files), (Some(Language::C), vec![]));
+        let files = paths(&["Main.java", "list.h"]);
+        assert_eq!(
+            Language::detect(&files),
+            (Some(Language::Java), vec![&files[1]])
+        );
+    }
+
+    #[test]
+    fn cpp_compiles_every_cpp_and_c_source() {
+        let files = paths(&["main.cxx"]);
+        assert_eq!(Language::detect(&files), (Some(Language::Cpp), vec![]));
+        assert_eq!(
+            Language::Cpp.source_extensions(),
+            vec!["cpp", "cc", "cxx", "c"]
+        );
+        assert_eq!(Language::C.source_extensions(), vec!["c"]);
+        assert_eq!(Language::built_with(&Compiler::Gxx), Some(Language::Cpp));
+    }
+
+    #[test]
+    fn explicit_c_rejects_cpp_files() {
+        let files = paths(&["main.c", "helper.cpp"]);
+        assert_eq!(Language::C.incompatible_files(&files), vec![&files[1]]);
+    }
+
+    #[test]
+    fn language_parsing() {
+        #[derive(Deserialize)]
+        struct Info {
+            language: Language,
+        }
+        let parse = |txt: &str| toml::from_str::<Info>(txt).unwrap().language;
+        assert_eq!(parse("language = 'c'"), Language::C);
+        assert_eq!(parse("language = 'cpp'"), Language::Cpp);
+        assert_eq!(parse("language = 'c++'"), Language::Cpp);
+        assert_eq!(parse("language = 'java'"), Language::Java);
+    }
+}
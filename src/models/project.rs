@@ -4,16 +4,22 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 
 use crate::core::{
-    file_utils::file_parser::{ParseError, ParseWarning},
+    compiler::compiler::Compiler,
+    file_utils::{
+        file_parser::{ParseError, ParseWarning},
+        file_utils::find_unlisted_folders,
+    },
     parser::{
+        discovery::add_discovered_folders,
         from_dir::FromDir,
         object_creator::{self, create_object_from_file, write_object_to_file},
     },
 };
 
 use super::{
-    constants::{COURSE_INFO_FILE, COURSE_STATE_FILE, EXO_STATE_FILE},
+    constants::{COURSE_INFO_FILE, COURSE_STATE_FILE, EXO_STATE_FILE, SKILL_INFO_FILE},
     exo::{Exo, ExoStateInfo},
+    exo_defaults::ExoDefaults,
     exo_state::ExoState,
     skill::Skill,
 };
@@ -22,8 +28,9 @@ use super::{
 pub struct Project {
     pub(crate) name: String,
     pub(crate) skills: Arc<Vec<Skill>>,
+    pub(crate) progression: Progression,
     pub(crate) state: ProjectState,
-    folder: std::path::PathBuf,
+    pub(crate) folder: std::path::PathBuf,
 }
 
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug)]
@@ -31,11 +38,28 @@ pub(crate) struct ProjectState {
     pub(crate) curr_skill_idx: usize,
     pub(crate) curr_exo_idx: usize,
 }
+/// How students move through the course
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Progression {
+    /// Every exo can be started, unless it has requirements
+    #[default]
+    Free,
+    /// Exos are unlocked one after the other, in the course order
+    Strict,
+}
 #[derive(Deserialize)]
 pub(crate) struct ProjectInfo {
     name: String,
-    #[serde(rename = "skills")]
+    #[serde(rename = "skills", default)]
     skill_folders: Vec<std::path::PathBuf>,
+    /// Adds the skill folders that are not listed in `skills`
+    #[serde(default)]
+    discover: bool,
+    #[serde(default)]
+    progression: Progression,
+    #[serde(flatten)]
+    defaults: ExoDefaults,
 }
 impl Project {
     /// returns the current exo pointed by the state
@@ -149,6 +173,118 @@ impl Project {
         self.set_curr_exo(0);
     }
 
+    /// Updates why each skill and exo is locked, from their requirements and the progression
+    /// This must be called again when the state of an exo changes
+    pub fn refresh_locks(&mut self) {
+        let strict = self.progression == Progression::Strict;
+        let mut locks = Vec::new();
+        // First skill and exo not done, everything after them is locked in strict progression
+        let mut unfinished_skill: Option<&Skill> = None;
+        let mut unfinished_exo: Option<&Exo> = None;
+        for skill in self.skills.iter() {
+            let skill_lock = self.unmet_requirements(&skill.requires).or_else(|| {
+                unfinished_skill
+                    .filter(|_| strict)
+                    .map(|unfinished| format!("Finish the skill {} first", unfinished.name))
+            });
+            let mut exo_locks = Vec::new();
+            for exo in skill.exos.iter() {
+                exo_locks.push(
+                    skill_lock
+                        .clone()
+                        .or_else(|| self.unmet_requirements(&exo.requires))
+                        .or_else(|| {
+                            unfinished_exo
+                                .filter(|_| strict)
+                                .map(|unfinished| format!("Finish {} first", unfinished.name))
+                        }),
+                );
+                if exo.state != ExoState::Done && unfinished_exo.is_none() {
+                    unfinished_exo = Some(exo);
+                }
+            }
+            if skill.done_exos() < skill.exos.len() && unfinished_skill.is_none() {
+                unfinished_skill = Some(skill);
+            }
+            locks.push((skill_lock, exo_locks));
+        }
+
+        let skills = Arc::make_mut(&mut self.skills);
+        for (skill, (skill_lock, exo_locks)) in skills.iter_mut().zip(locks) {
+            skill.locked = skill_lock;
+            let exos = Arc::make_mut(&mut skill.exos);
+            for (exo, exo_lock) in exos.iter_mut().zip(exo_locks) {
+                exo.locked = exo_lock;
+            }
+        }
+    }
+
+    /// Returns the reason of the lock if some of the `required` skills or exos are not done
+    /// Unknown folders are ignored, they are reported when parsing the course
+    fn unmet_requirements(&self, required: &[std::path::PathBuf]) -> Option<String> {
+        let unmet: Vec<&str> = required
+            .iter()
+            .filter_map(|folder| {
+                let folder = self.folder.join(folder);
+                if let Some(skill) = self.skills.iter().find(|skill| skill.path == folder) {
+                    return (skill.done_exos() < skill.exos.len()).then_some(skill.name.as_str());
+                }
+                self.skills
+                    .iter()
+                    .flat_map(|skill| skill.exos.iter())
+                    .find(|exo| exo.folder == folder && exo.state != ExoState::Done)
+                    .map(|exo| exo.name.as_str())
+            })
+            .collect();
+        if unmet.is_empty() {
+            return None;
+        }
+        Some(format!("Finish {} first", unmet.join(", ")))
+    }
+
+    /// Reports the requirements of skills and exos that are not a folder of the course
+    fn check_requirements(
+        dir: &std::path::Path,
+        skills: &[Skill],
+        warnings: &mut Vec<ParseWarning>,
+    ) {
+        let known = |folder: &std::path::PathBuf| {
+            skills.iter().any(|skill| {
+                skill.path == *folder || skill.exos.iter().any(|exo| exo.folder == *folder)
+            })
+        };
+        let requirements = skills.iter().flat_map(|skill| {
+            std::iter::once((&skill.path, &skill.requires))
+                .chain(skill.exos.iter().map(|exo| (&exo.folder, &exo.requires)))
+        });
+        for (folder, requires) in requirements {
+            for required in requires {
+                if !known(&dir.join(required)) {
+                    warnings.push(ParseWarning::UnknownRequirement(format!(
+                        "{:?} requires {:?} which is not a skill or an exo of the course",
+                        folder, required
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Collects the compilers needed to build the exos of every skill, without duplicates
+    pub fn compilers(&self) -> Vec<Compiler> {
+        let mut compilers = Vec::new();
+        for compiler in self
+            .skills
+            .iter()
+            .flat_map(|skill| skill.exos.iter())
+            .filter_map(|exo| exo.compiler())
+        {
+            if !compilers.contains(&compiler) {
+                compilers.push(compiler);
+            }
+        }
+        compilers
+    }
+
     /// Saves exo state to file
     fn save_exo_state(exo: &Exo, info: &ExoStateInfo) {
         if let Err(err) = write_object_to_file(&exo.folder.join(EXO_STATE_FILE), info) {
@@ -166,6 +302,25 @@ impl Project {
         info.state = state;
         Project::save_exo_state(exo, &info);
     }
+    /// Resets the progress of the exo, the favorite and the hints used are kept so teachers still
+    /// see who needed help
+    pub fn reset_exo_state(exo: &Exo) {
+        let previous = Project::read_exo_state_info(exo);
+        let info = ExoStateInfo {
+            favorite: previous.favorite,
+            hints_used: previous.hints_used,
+            ..Default::default()
+        };
+        Project::save_exo_state(exo, &info);
+    }
+    /// Counts one more hint used for the exo and stores it in file
+    /// Returns the number of hints used, never more than the hints of the exo
+    pub fn use_hint(exo: &Exo) -> usize {
+        let mut info = Project::read_exo_state_info(exo);
+        info.hints_used = (info.hints_used + 1).min(exo.hints.len());
+        Project::save_exo_state(exo, &info);
+        info.hints_used
+    }
     // Set exo as favorite or not and store it in file
     pub fn set_exo_favorite(exo: &Exo, is_favorite: bool) {
         let mut info = Project::read_exo_state_info(exo);
@@ -194,27 +349,42 @@ impl FromDir for Project {
                 .unwrap_or_default();
 
         // Using the skill folders found in the course.toml file, parse every skill
-        // /!\ Folders not found in the course.toml file are ignored /!\
-        // TODO maybe warn if there are folder that aren't included in course.toml ?
-        let mut warnings = Vec::new();
-        let skills = course_info
-            .skill_folders
+        // With `discover` the other skill folders are added after them, else they are ignored
+        let skill_folders = if course_info.discover {
+            add_discovered_folders(dir, &course_info.skill_folders, SKILL_INFO_FILE)
+        } else {
+            course_info.skill_folders.clone()
+        };
+        let mut warnings: Vec<ParseWarning> =
+            find_unlisted_folders(dir, &skill_folders, SKILL_INFO_FILE)
+                .into_iter()
+                .map(|folder| {
+                    ParseWarning::UnlistedFolder(format!(
+                        "Skill folder {:?} is not listed in {:?}",
+                        folder, course_info_file
+                    ))
+                })
+                .collect();
+        let defaults = course_info.defaults.relative_to(dir);
+        let skills = skill_folders
             .iter()
-            .filter_map(
-                |skill_folder| match Skill::from_dir(&dir.join(skill_folder)) {
+            .filter_map(|skill_folder| {
+                match Skill::from_dir_with_defaults(&dir.join(skill_folder), &defaults) {
                     Ok((skill, mut skill_warnings)) => {
                         warnings.append(&mut skill_warnings);
                         Some(skill)
                     }
-                    Err(error) => {
+                    Err((error, mut skill_warnings)) => {
+                        warnings.append(&mut skill_warnings);
                         warnings.push(ParseWarning::ParseSkillFail(format!(
-                            "Couldn't parse skill in {:?}: {:?}",
-                            skill_folder, error
+                            "Couldn't parse skill in {:?}: {}",
+                            dir.join(skill_folder),
+                            error
                         )));
                         None
                     }
-                },
-            )
+                }
+            })
             .collect::<Vec<Skill>>();
 
         if skills.is_empty() {
@@ -226,15 +396,16 @@ impl FromDir for Project {
                 warnings,
             ))
         } else {
-            Ok((
-                Self {
-                    name: course_info.name,
-                    skills: Arc::new(skills),
-                    state: project_state,
-                    folder: dir.to_path_buf(),
-                },
-                warnings,
-            ))
+            Project::check_requirements(dir, &skills, &mut warnings);
+            let mut project = Self {
+                name: course_info.name,
+                skills: Arc::new(skills),
+                progression: course_info.progression,
+                state: project_state,
+                folder: dir.to_path_buf(),
+            };
+            project.refresh_locks();
+            Ok((project, warnings))
         }
     }
 }
@@ -245,9 +416,11 @@ mod tests {
     use std::{str::FromStr, sync::Arc};
 
     use crate::models::{
-        check::{Check, CheckTest},
-        exo::Exo,
+        check::{Check, CheckTest, Comparison, ExpectedSource},
+        exo::{test::copy_example_exo, Exo},
+        exo_metadata::ExoMetadata,
         exo_state::ExoState,
+        language::Language,
     };
 
     use super::*;
@@ -279,6 +452,7 @@ mod tests {
                             instruction: Some(
                                 String::from("The 2 first program arguments are the firstname and number of legs of a dog. Print a full sentence about the dog. Make sure there is at least 2 arguments, print an error if not."),
                             ),
+                            language: Some(Language::C),
                             folder: "examples/mock-plx-project/intro/basic-args".into(),
                             state: ExoState::Todo,
                             files: vec![
@@ -295,11 +469,15 @@ mod tests {
                                         String::from("5"),
                                     ],
                                     test: CheckTest::Output{expected: String::from("The dog is Joe and has 5 legs")},
+                                    stdin: None,
+                                    source: ExpectedSource::Check(0),
                                 },
                                 Check {
                                     name: String::from("No arg -> error"),
                                     args: vec![],
                                     test: CheckTest::Output{ expected : String::from("Error: missing argument firstname and legs number")},
+                                    stdin: None,
+                                    source: ExpectedSource::Check(1),
                                 },
                                 Check {
                                     name: String::from("One arg -> error"),
@@ -307,13 +485,28 @@ mod tests {
                                         String::from("Joe"),
                                     ],
                                     test: CheckTest::Output {expected : String::from("Error: missing argument firstname and legs number")},
+                                    stdin: None,
+                                    source: ExpectedSource::Check(2),
                                 },
                             ],
                             favorite: false,
+                            hints: vec![],
+                            flags: vec![],
+                            shared: vec![],
+                            timeout: None,
+                            comparison: Comparison::Exact,
+                            hints_used: 0,
+                            metadata: ExoMetadata::default(),
+                            readonly: vec![],
+                            modified_readonly: vec![],
+                            reset_error: None,
+                            requires: vec![],
+                            locked: None,
                         },
                         Exo {
                             name: String::from("Basic output printing"),
                             folder: "examples/mock-plx-project/intro/basic-output".into(),
+                            language: Some(Language::C),
                             instruction: Some(
                                 String::from("Just print 2 lines"),
                             ),
@@ -329,17 +522,124 @@ mod tests {
                                     name: String::from("Lines are correct"),
                                     args: vec![],
                                     test: CheckTest::Output{ expected: String::from("PLX is amazing !\nThis is a neutral opinion...\n")},
+                                    stdin: None,
+                                    source: ExpectedSource::Check(0),
                                 },
                             ],
                             favorite: false,
+                            hints: vec![],
+                            flags: vec![],
+                            shared: vec![],
+                            timeout: None,
+                            comparison: Comparison::Exact,
+                            hints_used: 0,
+                            metadata: ExoMetadata::default(),
+                            readonly: vec![],
+                            modified_readonly: vec![],
+                            reset_error: None,
+                            requires: vec![],
+                            locked: None,
                         },
                     ]),
+                    requires: vec![],
+                    locked: None,
                 },
             ]),
+            progression: Progression::Free,
             state:ProjectState{curr_exo_idx: 0, curr_skill_idx:0}
         };
         let (actual, warnings) = project.unwrap();
         assert_eq!(expected, actual);
         assert!(matches!(warnings[0], ParseWarning::ParseSkillFail(_)));
     }
+
+    #[test]
+    fn hints_used_are_kept_after_reset() {
+        let (_dir, exo) = copy_example_exo("examples/mock-plx-project/mock-skill/hints-folder");
+
+        assert_eq!(Project::use_hint(&exo), 1);
+        Project::set_exo_favorite(&exo, true);
+        assert_eq!(Project::use_hint(&exo), 2);
+        assert_eq!(Project::use_hint(&exo), 2);
+        let (exo, _) = Exo::from_dir(&exo.folder).unwrap();
+        assert_eq!(exo.hints_used, 2);
+
+        Project::reset_exo_state(&exo);
+        let (exo, _) = Exo::from_dir(&exo.folder).unwrap();
+        assert_eq!(exo.hints_used, 2);
+        assert!(exo.favorite);
+    }
+
+    /// Loads a course of `mock-courses` with two skills of two exos
+    fn locked_course(course: &str) -> Project {
+        let path = std::path::PathBuf::from("examples/mock-plx-project/mock-courses").join(course);
+        let (project, _) = Project::from_dir(&path).unwrap();
+        project
+    }
+
+    fn exo_locks(project: &Project) -> Vec<Option<String>> {
+        project
+            .skills
+            .iter()
+            .flat_map(|skill| skill.exos.iter().map(|exo| exo.locked.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn strict_progression_unlocks_exos_in_order() {
+        let mut project = locked_course("strict-progression");
+        assert_eq!(
+            exo_locks(&project),
+            vec![
+                None,
+                Some(String::from("Finish one/a first")),
+                Some(String::from("Finish the skill One first")),
+                Some(String::from("Finish the skill One first")),
+            ]
+        );
+
+        for exo in Arc::make_mut(&mut Arc::make_mut(&mut project.skills)[0].exos) {
+            exo.state = ExoState::Done;
+        }
+        project.refresh_locks();
+        assert_eq!(
+            exo_locks(&project),
+            vec![None, None, None, Some(String::from("Finish two/c first"))]
+        );
+    }
+
+    #[test]
+    fn requirements_lock_skills_and_exos() {
+        let mut project = locked_course("required-exos");
+        assert_eq!(project.progression, Progression::Free);
+        assert_eq!(
+            project.skills[1].locked,
+            Some(String::from("Finish one/b first"))
+        );
+        assert_eq!(
+            exo_locks(&project),
+            vec![
+                None,
+                None,
+                Some(String::from("Finish one/b first")),
+                Some(String::from("Finish one/b first")),
+            ]
+        );
+
+        Arc::make_mut(&mut Arc::make_mut(&mut project.skills)[0].exos)[1].state = ExoState::Done;
+        project.refresh_locks();
+        assert_eq!(exo_locks(&project), vec![None; 4]);
+    }
+
+    #[test]
+    fn unknown_requirements_are_reported() {
+        let path =
+            std::path::PathBuf::from("examples/mock-plx-project/mock-courses/unknown-requirements");
+        let (_, warnings) = Project::from_dir(&path).unwrap();
+        let unknown: Vec<&ParseWarning> = warnings
+            .iter()
+            .filter(|warning| matches!(warning, ParseWarning::UnknownRequirement(_)))
+            .collect();
+        assert_eq!(unknown.len(), 1);
+    }
 }
@@ -1,11 +1,19 @@
 use std::sync::Arc;
 
 use crate::core::{
-    file_utils::file_parser::{ParseError, ParseWarning},
-    parser::{self, from_dir::FromDir},
+    file_utils::{
+        file_parser::{ParseError, ParseWarning},
+        file_utils::find_unlisted_folders,
+    },
+    parser::{self, discovery::add_discovered_folders, from_dir::FromDir},
 };
 
-use super::{constants::SKILL_INFO_FILE, exo::Exo, exo_state::ExoState};
+use super::{
+    constants::{EXO_INFO_FILE, SKILL_INFO_FILE},
+    exo::Exo,
+    exo_defaults::ExoDefaults,
+    exo_state::ExoState,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -13,12 +21,23 @@ pub struct Skill {
     pub name: String,
     pub path: std::path::PathBuf,
     pub exos: Arc<Vec<Exo>>,
+    /// Skill and exo folders to finish before this skill, relative to the course folder
+    pub requires: Vec<std::path::PathBuf>,
+    /// Why the skill can't be started yet, None if it's unlocked
+    pub locked: Option<String>,
 }
 #[derive(Deserialize, Serialize)]
 struct SkillInfo {
     name: String,
-    #[serde(rename = "exos")]
+    #[serde(rename = "exos", default)]
     exo_folders: Vec<std::path::PathBuf>,
+    /// Adds the exo folders that are not listed in `exos`
+    #[serde(default)]
+    discover: bool,
+    #[serde(default)]
+    requires: Vec<std::path::PathBuf>,
+    #[serde(flatten)]
+    defaults: ExoDefaults,
 }
 impl Skill {
     pub fn get_next_todo_exo(&self) -> Option<(usize, &Exo)> {
@@ -27,6 +46,14 @@ impl Skill {
             .enumerate()
             .find(|(_, exo)| exo.state == ExoState::Todo)
     }
+
+    /// Returns the number of exos done in this skill
+    pub fn done_exos(&self) -> usize {
+        self.exos
+            .iter()
+            .filter(|exo| exo.state == ExoState::Done)
+            .count()
+    }
 }
 impl FromDir for Skill {
     ///
@@ -36,30 +63,57 @@ impl FromDir for Skill {
     ///
     fn from_dir(
         dir: &std::path::PathBuf,
+    ) -> Result<(Self, Vec<ParseWarning>), (ParseError, Vec<ParseWarning>)> {
+        Skill::from_dir_with_defaults(dir, &ExoDefaults::default())
+    }
+}
+impl Skill {
+    /// Same as `from_dir`, the exo settings missing in skill.toml are taken from `defaults`, the
+    /// settings of the course
+    pub fn from_dir_with_defaults(
+        dir: &std::path::PathBuf,
+        defaults: &ExoDefaults,
     ) -> Result<(Self, Vec<ParseWarning>), (ParseError, Vec<ParseWarning>)> {
         // Get skill info by searching for the skill.toml file
         let skill_info_file = dir.join(SKILL_INFO_FILE);
         let info = parser::object_creator::create_object_from_file::<SkillInfo>(&skill_info_file)
             .map_err(|err| (err, vec![]))?;
+        let defaults = info.defaults.relative_to(dir).inherit(defaults);
 
-        // Using the exo folders found in the skill.toml file, parse every skill
-        // /!\ Folders not found in the skill.toml file are ignored /!\
-        // TODO maybe warn if there are folder that aren't included in skill.toml ?
-        let mut warnings = Vec::new();
-        let exos = info
-            .exo_folders
+        // Using the exo folders found in the skill.toml file, parse every exo
+        // With `discover` the other exo folders are added after them, else they are ignored
+        let exo_folders = if info.discover {
+            add_discovered_folders(dir, &info.exo_folders, EXO_INFO_FILE)
+        } else {
+            info.exo_folders.clone()
+        };
+        let mut warnings: Vec<ParseWarning> =
+            find_unlisted_folders(dir, &exo_folders, EXO_INFO_FILE)
+                .into_iter()
+                .map(|folder| {
+                    ParseWarning::UnlistedFolder(format!(
+                        "Exo folder {:?} is not listed in {:?}",
+                        folder, skill_info_file
+                    ))
+                })
+                .collect();
+        let exos = exo_folders
             .iter()
-            .filter_map(|exo_folder| match Exo::from_dir(&dir.join(exo_folder)) {
-                Ok((exo, mut exo_warnings)) => {
-                    warnings.append(&mut exo_warnings);
-                    Some(exo)
-                }
-                Err(err) => {
-                    warnings.push(ParseWarning::ParseExoFail(format!(
-                        "Couldn't Parse Exo {:?}",
-                        err
-                    )));
-                    None
+            .filter_map(|exo_folder| {
+                match Exo::from_dir_with_defaults(&dir.join(exo_folder), &defaults) {
+                    Ok((exo, mut exo_warnings)) => {
+                        warnings.append(&mut exo_warnings);
+                        Some(exo)
+                    }
+                    Err((err, mut exo_warnings)) => {
+                        warnings.append(&mut exo_warnings);
+                        warnings.push(ParseWarning::ParseExoFail(format!(
+                            "Couldn't parse exo in {:?}: {}",
+                            dir.join(exo_folder),
+                            err
+                        )));
+                        None
+                    }
                 }
             })
             .collect::<Vec<Exo>>();
@@ -75,9 +129,78 @@ impl FromDir for Skill {
                     name: info.name,
                     path: dir.to_path_buf(),
                     exos: Arc::new(exos),
+                    requires: info.requires,
+                    locked: None,
                 },
                 warnings,
             ))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::models::check::{CheckTest, Comparison};
+
+    use super::*;
+
+    #[test]
+    fn unlisted_exo_folders_are_reported() {
+        let path = PathBuf::from("examples/mock-plx-project/mock-skills/unlisted-exos");
+        let (skill, warnings) = Skill::from_dir(&path).unwrap();
+        assert_eq!(skill.exos.len(), 1);
+        let unlisted: Vec<&ParseWarning> = warnings
+            .iter()
+            .filter(|warning| matches!(warning, ParseWarning::UnlistedFolder(_)))
+            .collect();
+        assert_eq!(unlisted.len(), 1);
+        assert!(unlisted[0].to_string().contains("forgotten"));
+    }
+
+    #[test]
+    fn exo_folders_are_discovered() {
+        let path = PathBuf::from("examples/mock-plx-project/mock-skills/discovered-exos");
+        let (skill, warnings) = Skill::from_dir(&path).unwrap();
+        let names: Vec<&str> = skill.exos.iter().map(|exo| exo.name.as_str()).collect();
+        assert_eq!(names, vec!["First", "Second"]);
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, ParseWarning::UnlistedFolder(_))));
+    }
+
+    #[test]
+    fn done_exos_are_counted() {
+        let path = PathBuf::from("examples/mock-plx-project/mock-skills/exo-states");
+        let (skill, _) = Skill::from_dir(&path).unwrap();
+        assert_eq!(skill.done_exos(), 1);
+        assert_eq!(skill.exos[1].state, ExoState::InProgress);
+        assert!(skill.exos[1].favorite);
+    }
+
+    #[test]
+    fn exos_inherit_skill_and_course_settings() {
+        let course = ExoDefaults {
+            timeout: Some(10),
+            comparison: Some(Comparison::IgnoreCase),
+            ..Default::default()
+        };
+
+        let path = PathBuf::from("examples/mock-plx-project/mock-skills/inherited-settings");
+        let (skill, _) = Skill::from_dir_with_defaults(&path, &course).unwrap();
+        let (default, custom) = (&skill.exos[0], &skill.exos[1]);
+        assert_eq!(default.flags, vec!["-Wall"]);
+        assert_eq!(default.timeout, Some(std::time::Duration::from_secs(2)));
+        assert_eq!(default.comparison, Comparison::IgnoreCase);
+        assert_eq!(default.checks.len(), 1);
+        assert!(custom.flags.is_empty());
+        assert_eq!(custom.comparison, Comparison::IgnoreWhitespace);
+        assert_eq!(
+            custom.checks[0].test,
+            CheckTest::Output {
+                expected: String::from("custom")
+            }
+        );
+    }
+}
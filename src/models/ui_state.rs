@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
+use crate::core::compiler::diagnostic::Diagnostic;
+
 use super::{check_state::CheckState, exo::Exo, skill::Skill};
 
 // The list of states and associated values for the UI to represent
 #[derive(Debug, Clone, PartialEq)]
 pub enum UiState {
-    Home, // Home page with ASCII art
+    Home {
+        errors: Vec<String>,
+    }, // Home page with ASCII art, errors found at startup are shown there
     Quit, // Exit in progress
     Help {
         scroll_offset: usize,
@@ -38,12 +42,18 @@ pub enum UiState {
         scroll_offset: usize,
         exo: Arc<Exo>,
         error: String,
+        diagnostics: Vec<Diagnostic>, // Errors and warnings parsed from the compilation output
+        diagnostic_idx: usize,        // Index of the selected diagnostic
     },
     CheckResults {
         scroll_offset: usize,
         exo: Arc<Exo>,
         checks: Vec<CheckState>,
     },
+    ConfirmReset {
+        exo: Arc<Exo>,
+        last_state: Box<UiState>, // The train page shown behind the confirmation
+    },
     ShowSolution {
         scroll_offset: usize,
         exo: Arc<Exo>,                     // The current exo
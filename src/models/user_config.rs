@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    compiler::toolchain::Toolchain, file_utils::file_parser::ParseError,
+    parser::object_creator::create_object_from_file,
+};
+
+use super::constants::{USER_CONFIG_FILE, USER_CONFIG_FOLDER};
+
+/// Contains the user preferences that can be found in the PLX config file
+/// This file is shared by every course, see `UserConfig::file` for its location
+///
+/// ```toml
+/// [toolchain]
+/// c = "clang"
+/// cpp = "/usr/bin/clang++"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct UserConfig {
+    #[serde(default)]
+    pub toolchain: Toolchain,
+}
+
+impl UserConfig {
+    /// Gets the config file path
+    /// `$XDG_CONFIG_HOME/plx/config.toml` or `~/.config/plx/config.toml` on unix,
+    /// `%APPDATA%\plx\config.toml` on windows
+    pub fn file() -> Option<std::path::PathBuf> {
+        let config_folder = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(std::path::PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(std::path::PathBuf::from)
+                .or_else(|| {
+                    std::env::var_os("HOME")
+                        .map(|home| std::path::PathBuf::from(home).join(".config"))
+                })
+        }?;
+        Some(
+            config_folder
+                .join(USER_CONFIG_FOLDER)
+                .join(USER_CONFIG_FILE),
+        )
+    }
+
+    /// Loads the user config
+    /// The default config is used if the config file doesn't exist
+    pub fn load() -> Result<Self, ParseError> {
+        match UserConfig::file() {
+            Some(file) if file.exists() => create_object_from_file::<UserConfig>(&file),
+            _ => Ok(UserConfig::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::parser::toml_parser::toml_deserialize;
+
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_default_toolchain() {
+        let config: UserConfig = toml_deserialize("").unwrap();
+        assert_eq!(config, UserConfig::default());
+    }
+
+    #[test]
+    fn config_with_clang_toolchain() {
+        let config: UserConfig =
+            toml_deserialize("[toolchain]\nc = 'clang'\ncpp = 'clang++'").unwrap();
+        assert_eq!(config.toolchain.c, "clang");
+        assert_eq!(config.toolchain.cpp, "clang++");
+        assert_eq!(config.toolchain.make, "make");
+    }
+}
@@ -1,3 +1,5 @@
+mod highlight;
+mod markdown;
 mod pages;
 pub mod ui;
 mod utils;
@@ -0,0 +1,54 @@
+use std::sync::OnceLock;
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Style as SyntectStyle, Theme, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+const THEME_NAME: &str = "base16-eighties.dark";
+
+/// Syntaxes and theme are loaded once, loading them takes longer than rendering a frame
+fn syntaxes() -> &'static (SyntaxSet, Theme) {
+    static SYNTAXES: OnceLock<(SyntaxSet, Theme)> = OnceLock::new();
+    SYNTAXES.get_or_init(|| {
+        let mut theme_set = ThemeSet::load_defaults();
+        let theme = theme_set.themes.remove(THEME_NAME).unwrap_or_default();
+        (SyntaxSet::load_defaults_newlines(), theme)
+    })
+}
+
+/// Highlights `code` with the syntax of the files with this `extension` (`c`, `cpp`, `java`...)
+/// Code is kept as plain text when the extension is unknown
+pub fn highlight_code(code: &str, extension: Option<&str>) -> Vec<Line<'static>> {
+    let (syntax_set, theme) = syntaxes();
+    let syntax = extension
+        .and_then(|extension| syntax_set.find_syntax_by_token(extension))
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    LinesWithEndings::from(code)
+        .map(|line| {
+            let ranges: Vec<(SyntectStyle, &str)> = highlighter
+                .highlight_line(line, syntax_set)
+                .unwrap_or_else(|_| vec![(SyntectStyle::default(), line)]);
+            // Map syntect styles to ratatui styles, the new line is not part of the Line
+            let spans: Vec<Span> = ranges
+                .iter()
+                .map(|(style, text)| {
+                    let fg = style.foreground;
+                    Span::styled(
+                        text.trim_end_matches(['\n', '\r']).to_string(),
+                        Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                    )
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
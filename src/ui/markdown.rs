@@ -0,0 +1,281 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+};
+
+use super::highlight::highlight_code;
+
+/// Converts markdown to styled lines for the TUI
+/// Supports headings, bold, italic, strikethrough, lists, links, quotes, inline code and fenced
+/// code blocks, highlighted with the language of their fence or `default_extension`
+/// Single line breaks are kept, so plain text instructions are shown as written
+pub fn markdown_to_lines(
+    markdown: &str,
+    base: Style,
+    default_extension: Option<&str>,
+) -> Vec<Line<'static>> {
+    let mut renderer = MarkdownRenderer::new(base);
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        renderer.on_event(event, default_extension);
+    }
+    renderer.finish()
+}
+
+struct MarkdownRenderer {
+    lines: Vec<Line<'static>>,
+    /// Spans of the line being built
+    spans: Vec<Span<'static>>,
+    /// Styles of the nested tags, the last one applies to the text
+    styles: Vec<Style>,
+    /// Next number of each nested list, None for bullet lists
+    lists: Vec<Option<u64>>,
+    /// Prefix of the next line, e.g. `- ` at the start of a list item
+    prefix: Option<String>,
+    /// Language and content of the code block being read
+    code_block: Option<(String, String)>,
+    quote_depth: usize,
+}
+
+impl MarkdownRenderer {
+    fn new(base: Style) -> Self {
+        Self {
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: vec![base],
+            lists: Vec::new(),
+            prefix: None,
+            code_block: None,
+            quote_depth: 0,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(self.style().patch(style));
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    /// Indentation of the lines inside lists and quotes
+    fn indent(&self) -> String {
+        "│ ".repeat(self.quote_depth) + &"  ".repeat(self.lists.len().saturating_sub(1))
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.spans.is_empty() {
+            let prefix = self.prefix.take().unwrap_or_else(|| {
+                let indent = self.indent();
+                // Continuation lines of list items are aligned with their text
+                if self.lists.is_empty() {
+                    indent
+                } else {
+                    indent + "  "
+                }
+            });
+            if !prefix.is_empty() {
+                self.spans.push(Span::from(prefix).dim());
+            }
+        }
+        self.spans
+            .push(Span::styled(text.to_string(), self.style()));
+    }
+
+    /// Ends the current line, if it contains anything
+    fn flush(&mut self) {
+        if !self.spans.is_empty() {
+            self.lines.push(Line::from(std::mem::take(&mut self.spans)));
+        }
+    }
+
+    /// Separates blocks with an empty line, except inside lists
+    fn end_block(&mut self) {
+        self.flush();
+        if self.lists.is_empty() && self.lines.last().is_some_and(|line| !line.spans.is_empty()) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn on_event(&mut self, event: Event, default_extension: Option<&str>) {
+        if let Some((_, code)) = &mut self.code_block {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => self.end_code_block(default_extension),
+                _ => {}
+            }
+            return;
+        }
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag) => self.end_tag(tag),
+            Event::Text(text) => self.push_text(&text),
+            Event::Code(code) => {
+                self.push_style(Style::new().fg(Color::Yellow));
+                self.push_text(&code);
+                self.pop_style();
+            }
+            Event::SoftBreak | Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.lines.push(Line::from("─".repeat(40)).dim());
+            }
+            _ => {}
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                self.push_style(match level {
+                    HeadingLevel::H1 => style.add_modifier(Modifier::UNDERLINED),
+                    _ => style,
+                });
+            }
+            Tag::Emphasis => self.push_style(Style::new().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::new().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(Style::new().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { .. } => self.push_style(
+                Style::new()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+                self.push_style(Style::new().add_modifier(Modifier::ITALIC));
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let indent = self.indent();
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => String::from("- "),
+                };
+                self.prefix = Some(indent + &bullet);
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((language, String::new()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.pop_style();
+                self.end_block();
+            }
+            TagEnd::Paragraph => self.end_block(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link => {
+                self.pop_style()
+            }
+            TagEnd::BlockQuote(_) => {
+                self.pop_style();
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.end_block();
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::List(_) => {
+                self.lists.pop();
+                self.end_block();
+            }
+            _ => {}
+        }
+    }
+
+    fn end_code_block(&mut self, default_extension: Option<&str>) {
+        let Some((language, code)) = self.code_block.take() else {
+            return;
+        };
+        let extension = if language.is_empty() {
+            default_extension
+        } else {
+            Some(language.as_str())
+        };
+        let indent = self.indent();
+        for mut line in highlight_code(&code, extension) {
+            line.spans.insert(0, Span::from(format!("{}  ", indent)));
+            self.lines.push(line);
+        }
+        self.end_block();
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush();
+        while self.lines.last().is_some_and(|line| line.spans.is_empty()) {
+            self.lines.pop();
+        }
+        self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn plain_text_keeps_its_lines() {
+        let lines = markdown_to_lines("First line\nSecond line", Style::new(), None);
+        assert_eq!(texts(&lines), vec!["First line", "Second line"]);
+    }
+
+    #[test]
+    fn blocks_are_formatted() {
+        let markdown = "# Title\n\nSome **bold** and `code`\n\n1. One\n2. Two\n   - Nested\n\n```c\nint a;\n```\n";
+        let lines = markdown_to_lines(markdown, Style::new(), None);
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "Title",
+                "",
+                "Some bold and code",
+                "",
+                "1. One",
+                "2. Two",
+                "  - Nested",
+                "",
+                "  int a;"
+            ]
+        );
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert!(lines[2].spans[1]
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD));
+        assert_eq!(lines[2].spans[3].style.fg, Some(Color::Yellow));
+    }
+}
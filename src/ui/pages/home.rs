@@ -9,7 +9,7 @@ use ratatui::{
     Frame,
 };
 
-pub fn render_home(frame: &mut Frame) {
+pub fn render_home(frame: &mut Frame, errors: &Vec<String>) {
     // Generate a gradient manually on ASCII_LOGO from left to right
     // Split the text in lines and columns to distribute the color of the gradient
     let gradient_width = ASCII_LOGO.lines().map(str::len).max().unwrap_or_default();
@@ -36,6 +36,15 @@ pub fn render_home(frame: &mut Frame) {
     lines.push(Line::from("Repository: https://github.com/plx-pdg/plx").dim());
     lines.push(Line::from("Website: https://plx.rs").dim());
 
+    // Append errors found at startup, like a missing compiler
+    if !errors.is_empty() {
+        lines.push(Line::default()); //margin top
+        lines.push(Line::from("Errors").bold().red());
+        for error in errors {
+            lines.push(Line::from(error.clone()).red());
+        }
+    }
+
     // Append quick help
     let quick_help_lines = vec![
         "Type r to resume progress",
@@ -7,7 +7,7 @@ use ratatui::widgets::{Clear, Wrap};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
@@ -17,6 +17,7 @@ use std::sync::Arc;
 const DEFAULT_STYLE: Style = Style::new();
 const SELECTED_BORDER: Style = Style::new().fg(Color::Blue);
 const SELECTED_STYLE: Style = Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD);
+const LOCKED_COLOR: Color = Color::DarkGray;
 
 /// Render the 2 lists with a popup preview showing content from train::render_exo()
 pub fn render_preview(
@@ -57,7 +58,7 @@ pub fn render_lists(
         "Skills",
         &skills
             .iter()
-            .map(|skill| Line::from(skill.name.clone()))
+            .map(|skill| item_line(&skill.name, &skill.locked, Color::default()))
             .collect::<Vec<Line>>(),
         Some(*skill_index),
         is_skill_selection,
@@ -70,13 +71,17 @@ pub fn render_lists(
         &exos
             .iter()
             .map(|exo| {
-                Line::from(exo.name.clone()).fg(match exo.state {
-                    //TODO: enable colors when states are fully managed
-                    // ExoState::Todo => Color::default(),
-                    // ExoState::InProgress => EXO_INPROGRESS_COLOR,
-                    // ExoState::Done => EXO_DONE_COLOR,
-                    _ => Color::default(),
-                })
+                item_line(
+                    &exo.name,
+                    &exo.locked,
+                    match exo.state {
+                        //TODO: enable colors when states are fully managed
+                        // ExoState::Todo => Color::default(),
+                        // ExoState::InProgress => EXO_INPROGRESS_COLOR,
+                        // ExoState::Done => EXO_DONE_COLOR,
+                        _ => Color::default(),
+                    },
+                )
             })
             .collect::<Vec<Line>>(),
         exo_index,
@@ -84,6 +89,18 @@ pub fn render_lists(
     );
 }
 
+/// Line of a skill or an exo, greyed out with the reason when it's locked
+fn item_line<'a>(name: &str, locked: &Option<String>, color: Color) -> Line<'a> {
+    match locked {
+        Some(reason) => Line::from(vec![
+            Span::from(name.to_string()),
+            Span::from(format!("  {}", reason)).italic(),
+        ])
+        .fg(LOCKED_COLOR),
+        None => Line::from(name.to_string()).fg(color),
+    }
+}
+
 /// Renders a list of items with optional selection highlighting
 fn render_list(
     frame: &mut Frame,
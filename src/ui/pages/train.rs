@@ -3,19 +3,25 @@
 /// that generate the render_common_top() to show exo metadata that should be always visible
 use std::sync::Arc;
 
-use crate::models::{
-    check::CheckTest,
-    check_state::{CheckState, CheckStatus},
-    exo::Exo,
+use crate::{
+    core::compiler::diagnostic::{Diagnostic, DiagnosticLevel},
+    models::{
+        check::CheckTest,
+        check_state::{CheckState, CheckStatus},
+        exo::Exo,
+        exo_metadata::ExoMetadata,
+    },
 };
 use ansi_to_tui::IntoText;
 use ratatui::{
-    style::{Color, Stylize},
+    style::{Color, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Paragraph, Wrap},
+    widgets::{Block, Clear, Paragraph, Wrap},
     Frame,
 };
 
+use crate::ui::{markdown::markdown_to_lines, utils::popup_area};
+
 // Show the "Compiling" message without the checks
 pub fn render_compilation(frame: &mut Frame, exo: &Arc<Exo>) {
     let mut bottom: Vec<Line> = vec![];
@@ -65,12 +71,51 @@ fn push_ansi_content(lines: &mut Vec<Line>, ansi_string: String, offset: usize)
     }
 }
 
+/// Push one line per diagnostic, the selected one is highlighted
+fn push_diagnostics(
+    lines: &mut Vec<Line>,
+    exo: &Arc<Exo>,
+    diagnostics: &[Diagnostic],
+    diagnostic_idx: usize,
+) {
+    for (i, diagnostic) in diagnostics.iter().enumerate() {
+        let file = diagnostic
+            .file
+            .strip_prefix(&exo.folder)
+            .unwrap_or(&diagnostic.file);
+        let level = match diagnostic.level {
+            DiagnosticLevel::Error => Span::from(diagnostic.level.name()).red(),
+            DiagnosticLevel::Warning => Span::from(diagnostic.level.name()).magenta(),
+        };
+        let mut line = Line::from(vec![
+            Span::from(if i == diagnostic_idx { "> " } else { "  " }),
+            Span::from(format!(
+                "{}:{}:{} ",
+                file.display(),
+                diagnostic.line,
+                diagnostic.column
+            ))
+            .bold(),
+            level,
+            Span::from(format!(": {}", diagnostic.message)),
+        ]);
+        if i == diagnostic_idx {
+            line = line.reversed();
+        }
+        lines.push(line);
+    }
+    lines.push(Line::from("n/p to select, Enter to open it in your editor").dim());
+    lines.push(Line::from(""));
+}
+
 // Show the compilation errors, basic support of scroll_offset
 pub fn render_compilation_error(
     frame: &mut Frame,
     exo: &Arc<Exo>,
     scroll_offset: &usize,
     error: &String,
+    diagnostics: &[Diagnostic],
+    diagnostic_idx: usize,
 ) {
     // Compilation title with offset if > 0
     let mut bottom: Vec<Line> = vec![];
@@ -82,6 +127,10 @@ pub fn render_compilation_error(
     }
     bottom.push(compilation_title_with_offset);
 
+    if !diagnostics.is_empty() {
+        push_diagnostics(&mut bottom, exo, diagnostics, diagnostic_idx);
+    }
+
     // Show lines of compilation output, each is its own Line
     push_ansi_content(
         &mut bottom,
@@ -137,6 +186,13 @@ pub fn render_check_results(
                         );
                         bottom.push(l);
                     }
+                    if let Some(stdin) = &check_state.check.stdin {
+                        push_each_line_or_empty_info(
+                            &mut bottom,
+                            "Stdin".to_string(),
+                            stdin.clone(),
+                        );
+                    }
                     // If one value is empty, maybe showing a diff is not useful
                     if output.trim().is_empty() || expected.trim().is_empty() {
                         push_each_line_or_empty_info(&mut bottom, "Output".to_string(), output);
@@ -165,34 +221,137 @@ pub fn render_check_results(
 // The common top part with exo name and instruction
 pub fn render_exo(lines: &mut Vec<Line>, exo: &Arc<Exo>, include_exo_files: bool) {
     lines.push(Line::from(exo.name.clone()).cyan().bold());
+    if include_exo_files {
+        push_metadata(lines, &exo.metadata);
+        if let Some(reason) = &exo.locked {
+            lines.push(Line::from(format!("Locked: {}", reason)).red());
+        }
+    }
     if let Some(instr) = &exo.instruction {
-        // Make sure each line has a real Line because newlines are dropped otherwise
-        instr
-            .clone()
-            .lines()
-            .for_each(|l| lines.push(Line::from(l.to_string()).magenta()));
+        // Code blocks without a language are highlighted like the exo files
+        lines.append(&mut markdown_to_lines(
+            instr,
+            Style::new().magenta(),
+            exo.language.map(|language| language.syntax_extension()),
+        ));
+    }
+    for file in exo.modified_readonly.iter() {
+        lines.push(
+            Line::from(format!(
+                "{} is read-only and was modified, x resets the exo",
+                file_name(file)
+            ))
+            .red(),
+        );
+    }
+    if let Some(err) = &exo.reset_error {
+        lines.push(Line::from(err.clone()).red());
     }
     if !include_exo_files {
         return;
     }
     lines.push(Line::default());
     lines.push(Line::from("Exo files").cyan().bold());
-    exo.files.clone().iter().for_each(|f| {
-        lines.push(Line::from(
-            f.file_name()
-                .unwrap_or_default()
-                .to_os_string() // TODO: why do we need to do this ? We unwrapped
-                // Option<OsStr> and we don't have access to into_string() ????
-                .into_string()
-                .unwrap_or_default(), //TODO: how can we do this in less code ??
-        ))
-    });
+    exo.editable_files()
+        .iter()
+        .for_each(|f| lines.push(Line::from(file_name(f))));
+    if !exo.readonly.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from("Provided files").cyan().bold());
+        exo.readonly
+            .iter()
+            .for_each(|f| lines.push(Line::from(file_name(f)).dim()));
+    }
+}
+
+/// Name of the file without its folder
+fn file_name(file: &std::path::Path) -> String {
+    file.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Push the difficulty, duration and tags of the exo on a line, then its authors and source
+fn push_metadata(lines: &mut Vec<Line>, metadata: &ExoMetadata) {
+    let mut summary: Vec<String> = Vec::new();
+    if let Some(difficulty) = metadata.difficulty {
+        summary.push(difficulty.to_string());
+    }
+    if let Some(minutes) = metadata.estimated_minutes {
+        summary.push(format!("~{} min", minutes));
+    }
+    if !metadata.tags.is_empty() {
+        summary.push(metadata.tags.join(", "));
+    }
+    if !summary.is_empty() {
+        lines.push(Line::from(summary.join(" · ")).dim());
+    }
+    let mut credits: Vec<String> = Vec::new();
+    if !metadata.authors.is_empty() {
+        credits.push(format!("By {}", metadata.authors.join(", ")));
+    }
+    if let Some(source) = &metadata.source {
+        credits.push(format!("From {}", source));
+    }
+    if !credits.is_empty() {
+        lines.push(Line::from(credits.join(" · ")).dim().italic());
+    }
+}
+
+/// Push the hints revealed by the student and how to reveal the next one
+fn push_hints(lines: &mut Vec<Line>, exo: &Arc<Exo>) {
+    if exo.hints.is_empty() {
+        return;
+    }
+    lines.push(Line::default());
+    lines.push(
+        Line::from(format!("Hints {}/{}", exo.hints_used, exo.hints.len()))
+            .yellow()
+            .bold(),
+    );
+    for hint in exo.hints.iter().take(exo.hints_used) {
+        lines.append(&mut markdown_to_lines(
+            hint,
+            Style::new().yellow(),
+            exo.language.map(|language| language.syntax_extension()),
+        ));
+    }
+    if exo.hints_used < exo.hints.len() {
+        lines.push(Line::from("i to show the next hint").dim());
+    }
+}
+
+/// Show a popup over the train page asking to confirm the reset of the exo files
+pub fn render_reset_confirmation(frame: &mut Frame, exo: &Arc<Exo>) {
+    let lines = vec![
+        Line::from(format!("Reset {} ?", exo.name)).bold().yellow(),
+        Line::default(),
+        Line::from("The exo files will be replaced by their original version,"),
+        Line::from("your changes will be lost."),
+        Line::default(),
+        Line::from(vec![
+            Span::from("enter").green(),
+            Span::from(" to reset, ").dim(),
+            Span::from("esc").green(),
+            Span::from(" to cancel").dim(),
+        ]),
+    ];
+    let area = popup_area(frame.area(), 60, 30);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Text::from(lines))
+            .block(Block::bordered().title("Reset exo"))
+            .wrap(Wrap { trim: true }),
+        area,
+    );
 }
 
 // Render final page with the common top and specific bottom
 fn render_train(frame: &mut Frame, exo: &Arc<Exo>, bottom: Vec<Line>) {
     let mut lines: Vec<Line> = vec![];
     render_exo(&mut lines, exo, false);
+    push_hints(&mut lines, exo);
     lines.push(Line::default());
     bottom.iter().for_each(|l| lines.push(l.clone()));
     let zone = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: true });
@@ -55,7 +55,7 @@ impl Ui {
 
     fn render_frame(&self, frame: &mut Frame, state: &UiState) {
         match state {
-            UiState::Home => home::render_home(frame),
+            UiState::Home { errors } => home::render_home(frame, errors),
             UiState::Help { scroll_offset, .. } => help::render_help(frame, *scroll_offset),
             UiState::SkillSelection {
                 skill_index,
@@ -80,7 +80,16 @@ impl Ui {
                 scroll_offset,
                 exo,
                 error,
-            } => train::render_compilation_error(frame, exo, scroll_offset, error),
+                diagnostics,
+                diagnostic_idx,
+            } => train::render_compilation_error(
+                frame,
+                exo,
+                scroll_offset,
+                error,
+                diagnostics,
+                *diagnostic_idx,
+            ),
             UiState::CheckResults {
                 scroll_offset,
                 exo,
@@ -93,6 +102,10 @@ impl Ui {
                 solution_idx,
                 ..
             } => solution::render_solution(frame, exo, solution, solution_path, solution_idx),
+            UiState::ConfirmReset { exo, last_state } => {
+                self.render_frame(frame, last_state);
+                train::render_reset_confirmation(frame, exo);
+            }
             UiState::Quit => return,
         }
     }
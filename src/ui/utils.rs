@@ -44,6 +44,8 @@ pub fn ui_key_to_core_key(key: &KeyCode) -> Option<Key> {
         KeyCode::Char('r') => Some(Key::R),
         KeyCode::Char('p') => Some(Key::P),
         KeyCode::Char('n') => Some(Key::N),
+        KeyCode::Char('x') => Some(Key::X),
+        KeyCode::Char('i') => Some(Key::I),
         KeyCode::Enter => Some(Key::Enter),
         KeyCode::Esc => Some(Key::Esc),
         KeyCode::Char('?') => Some(Key::Interrogation),